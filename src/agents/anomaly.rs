@@ -0,0 +1,292 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{debug, warn};
+
+use crate::clients::{PodMetricHistory, PodMetrics, PrometheusClient};
+use crate::crd::{AnomalyDetectionConfig, AnomalyScore, BaselineMethod, TriggerReason};
+use crate::error::Result;
+use crate::models::WorkloadRef;
+
+const SEASONAL_OFFSET_DAYS: i64 = 7;
+const MIN_STDDEV: f64 = 1e-9;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Baseline {
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl Baseline {
+    pub fn rolling(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+
+        Some(Self {
+            mean,
+            stddev: variance.sqrt(),
+        })
+    }
+
+    pub fn ewma(values: &[f64], alpha: f64) -> Option<Self> {
+        let (first, rest) = values.split_first()?;
+        let alpha = alpha.clamp(0.0, 1.0);
+
+        let mut mean = *first;
+        let mut variance = 0.0;
+        for value in rest {
+            let diff = value - mean;
+            mean += alpha * diff;
+            variance = (1.0 - alpha) * (variance + alpha * diff * diff);
+        }
+
+        Some(Self {
+            mean,
+            stddev: variance.sqrt(),
+        })
+    }
+
+    pub fn z_score(&self, value: f64) -> Option<f64> {
+        if self.stddev < MIN_STDDEV {
+            return None;
+        }
+        Some((value - self.mean) / self.stddev)
+    }
+}
+
+pub struct AnomalyDetector {
+    prometheus: Arc<PrometheusClient>,
+    config: AnomalyDetectionConfig,
+}
+
+impl AnomalyDetector {
+    pub fn new(prometheus: Arc<PrometheusClient>, config: AnomalyDetectionConfig) -> Self {
+        Self { prometheus, config }
+    }
+
+    pub async fn detect(
+        &self,
+        namespace: &str,
+        current: &HashMap<String, PodMetrics>,
+        workloads: &HashMap<String, WorkloadRef>,
+    ) -> HashMap<String, Vec<(TriggerReason, AnomalyScore)>> {
+        let history = match self.history(namespace).await {
+            Ok(history) => by_workload(history, workloads),
+            Err(e) => {
+                warn!(
+                    "Failed to load metric history for anomaly detection in {}: {}",
+                    namespace, e
+                );
+                return HashMap::new();
+            }
+        };
+
+        let mut anomalies: HashMap<String, Vec<(TriggerReason, AnomalyScore)>> = HashMap::new();
+
+        for (pod_name, metrics) in current {
+            let key = workloads
+                .get(pod_name)
+                .map(|workload| workload.to_string())
+                .unwrap_or_else(|| pod_name.clone());
+            let pod_history = match history.get(&key) {
+                Some(pod_history) => pod_history,
+                None => continue,
+            };
+
+            for (metric, reason) in [
+                ("cpu", TriggerReason::HighCpu),
+                ("memory", TriggerReason::HighMemory),
+                ("errorRate", TriggerReason::HighErrorRate),
+                ("latency", TriggerReason::HighLatency),
+            ] {
+                let score = match (metrics.signal(metric), pod_history.get(metric)) {
+                    (Some(value), Some(samples)) => {
+                        let values: Vec<f64> = samples.iter().map(|(_, v)| *v).collect();
+                        self.score(metric, value, &values)
+                    }
+                    _ => None,
+                };
+
+                if let Some(score) = score {
+                    if score.z_score >= self.config.z_score_threshold {
+                        debug!(
+                            "Anomalous {} on pod {}/{}: {:.3} vs baseline {:.3}±{:.3} (z={:.2})",
+                            metric,
+                            namespace,
+                            pod_name,
+                            score.value,
+                            score.baseline_mean,
+                            score.baseline_stddev,
+                            score.z_score
+                        );
+                    }
+                    anomalies
+                        .entry(pod_name.clone())
+                        .or_default()
+                        .push((reason, score));
+                }
+            }
+        }
+
+        anomalies
+    }
+
+    fn score(&self, metric: &str, value: f64, samples: &[f64]) -> Option<AnomalyScore> {
+        if samples.len() < self.config.min_samples {
+            return None;
+        }
+
+        let baseline = match self.config.method {
+            BaselineMethod::Rolling | BaselineMethod::Seasonal => Baseline::rolling(samples),
+            BaselineMethod::Ewma => Baseline::ewma(samples, self.config.ewma_alpha),
+        }?;
+
+        Some(AnomalyScore {
+            metric: metric.to_string(),
+            value,
+            baseline_mean: baseline.mean,
+            baseline_stddev: baseline.stddev,
+            z_score: baseline.z_score(value)?,
+            method: self.config.method,
+        })
+    }
+
+    async fn history(&self, namespace: &str) -> Result<PodMetricHistory> {
+        let lookback = ChronoDuration::minutes(self.config.lookback_minutes as i64);
+        let step = self.config.step_seconds.max(1);
+        let now = Utc::now();
+
+        let (start, end) = match self.config.method {
+            BaselineMethod::Seasonal => {
+                let anchor = now - ChronoDuration::days(SEASONAL_OFFSET_DAYS);
+                (anchor - lookback / 2, anchor + lookback / 2)
+            }
+            BaselineMethod::Rolling | BaselineMethod::Ewma => {
+                (now - lookback, now - ChronoDuration::seconds(step as i64))
+            }
+        };
+
+        self.prometheus
+            .get_pod_metric_history(namespace, start, end, step)
+            .await
+    }
+}
+
+// Pods are replaced over time, so history is pooled across every pod a workload has
+// run. Past pods are matched to the current workloads by their generated names.
+fn by_workload(
+    history: PodMetricHistory,
+    workloads: &HashMap<String, WorkloadRef>,
+) -> HashMap<String, HashMap<&'static str, Vec<(DateTime<Utc>, f64)>>> {
+    let mut pooled: HashMap<String, HashMap<&'static str, Vec<(DateTime<Utc>, f64)>>> =
+        HashMap::new();
+
+    for (pod_name, metrics) in history {
+        let key = workloads
+            .get(&pod_name)
+            .or_else(|| {
+                workloads
+                    .values()
+                    .find(|workload| generated_by(&pod_name, workload))
+            })
+            .map(|workload| workload.to_string())
+            .unwrap_or(pod_name);
+
+        let entry = pooled.entry(key).or_default();
+        for (metric, samples) in metrics {
+            entry.entry(metric).or_default().extend(samples);
+        }
+    }
+
+    for metrics in pooled.values_mut() {
+        for samples in metrics.values_mut() {
+            samples.sort_by_key(|(at, _)| *at);
+        }
+    }
+
+    pooled
+}
+
+fn generated_by(pod_name: &str, workload: &WorkloadRef) -> bool {
+    let suffix = match pod_name
+        .strip_prefix(workload.name.as_str())
+        .and_then(|rest| rest.strip_prefix('-'))
+    {
+        Some(suffix) => suffix,
+        None => return false,
+    };
+    let generated =
+        |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric());
+
+    match workload.kind.as_str() {
+        "StatefulSet" => !suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_digit()),
+        "Deployment" | "Rollout" => suffix
+            .split_once('-')
+            .is_some_and(|(hash, id)| generated(hash) && generated(id)),
+        _ => generated(suffix),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_baselines_and_z_score() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0];
+
+        let rolling = Baseline::rolling(&values).unwrap();
+        assert!((rolling.mean - 3.0).abs() < 1e-9);
+        assert!((rolling.stddev - 2.0_f64.sqrt()).abs() < 1e-9);
+        assert!((rolling.z_score(3.0 + 2.0 * rolling.stddev).unwrap() - 2.0).abs() < 1e-9);
+
+        let flat = Baseline::rolling(&[2.0, 2.0, 2.0]).unwrap();
+        assert_eq!(flat.z_score(10.0), None);
+
+        let ewma = Baseline::ewma(&values, 0.5).unwrap();
+        assert!(ewma.mean > rolling.mean);
+        assert!(ewma.mean < 5.0);
+        assert!(Baseline::ewma(&[], 0.5).is_none());
+    }
+
+    #[test]
+    fn test_history_pooled_by_workload() {
+        let web = WorkloadRef {
+            api_version: "apps/v1".to_string(),
+            kind: "Deployment".to_string(),
+            name: "web".to_string(),
+        };
+        let db = WorkloadRef {
+            api_version: "apps/v1".to_string(),
+            kind: "StatefulSet".to_string(),
+            name: "db".to_string(),
+        };
+        let workloads = HashMap::from([
+            ("web-7d9f8c6b5-x2k4p".to_string(), web.clone()),
+            ("db-0".to_string(), db.clone()),
+        ]);
+
+        let now = Utc::now();
+        let history: PodMetricHistory = [
+            ("web-5f6d7c8b9-abcde", now, 1.0),
+            ("web-5f6d7c8b9-fghij", now - ChronoDuration::minutes(1), 2.0),
+            ("web-api-5f6d7c8b9-klmno", now, 9.0),
+            ("db-1", now, 3.0),
+        ]
+        .into_iter()
+        .map(|(pod, at, value)| (pod.to_string(), HashMap::from([("cpu", vec![(at, value)])])))
+        .collect();
+
+        let pooled = by_workload(history, &workloads);
+        let values =
+            |key: &str| -> Vec<f64> { pooled[key]["cpu"].iter().map(|(_, v)| *v).collect() };
+        assert_eq!(values("Deployment/web"), vec![2.0, 1.0]);
+        assert_eq!(values("StatefulSet/db"), vec![3.0]);
+        assert_eq!(values("web-api-5f6d7c8b9-klmno"), vec![9.0]);
+    }
+}
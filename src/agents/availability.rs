@@ -0,0 +1,271 @@
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet};
+use k8s_openapi::api::core::v1::Pod;
+use k8s_openapi::api::policy::v1::PodDisruptionBudget;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
+use kube::api::ListParams;
+use kube::{Api, Client as KubeClient, ResourceExt};
+use std::collections::BTreeMap;
+use tracing::debug;
+
+use super::workload::WorkloadResolver;
+use crate::error::Result;
+use crate::models::WorkloadRef;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Availability {
+    Allowed,
+    Degraded(String),
+    Refused(String),
+}
+
+#[derive(Clone, Debug)]
+struct Replicas {
+    workload: String,
+    desired: i32,
+    ready: i32,
+}
+
+pub struct AvailabilityGuard {
+    kube_client: KubeClient,
+    workloads: WorkloadResolver,
+}
+
+impl AvailabilityGuard {
+    pub fn new(kube_client: KubeClient) -> Self {
+        Self {
+            workloads: WorkloadResolver::new(kube_client.clone()),
+            kube_client,
+        }
+    }
+
+    pub async fn assess(&self, namespace: &str, pod_name: &str) -> Result<Availability> {
+        let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
+        let pod = pods.get(pod_name).await?;
+
+        if !is_ready(&pod) {
+            debug!(
+                "Pod {}/{} is not ready, disrupting it does not reduce availability",
+                namespace, pod_name
+            );
+            return Ok(Availability::Allowed);
+        }
+
+        let replicas = self.replicas(namespace, &pod).await?;
+        if replicas.ready <= 1 {
+            return Ok(Availability::Refused(format!(
+                "pod {} is the last ready replica of {} ({} desired)",
+                pod_name, replicas.workload, replicas.desired
+            )));
+        }
+
+        let budgets: Api<PodDisruptionBudget> =
+            Api::namespaced(self.kube_client.clone(), namespace);
+        for budget in budgets.list(&ListParams::default()).await?.items {
+            let selects = budget
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.selector.as_ref())
+                .is_some_and(|selector| selector_matches(selector, pod.labels()));
+            if !selects {
+                continue;
+            }
+
+            let status = budget.status.as_ref();
+            let disruptions_allowed = status.map(|s| s.disruptions_allowed).unwrap_or(0);
+            if disruptions_allowed <= 0 {
+                return Ok(Availability::Degraded(format!(
+                    "PodDisruptionBudget {} allows no further disruptions ({} of {} desired pods healthy)",
+                    budget.name_any(),
+                    status.map(|s| s.current_healthy).unwrap_or(0),
+                    status.map(|s| s.desired_healthy).unwrap_or(0)
+                )));
+            }
+        }
+
+        debug!(
+            "Disrupting pod {}/{} is safe: {} has {}/{} ready replicas",
+            namespace, pod_name, replicas.workload, replicas.ready, replicas.desired
+        );
+
+        Ok(Availability::Allowed)
+    }
+
+    pub async fn assess_scale_up(
+        &self,
+        namespace: &str,
+        workload: &WorkloadRef,
+    ) -> Result<Availability> {
+        let replicas = self.workload_replicas(namespace, workload).await?;
+        if replicas.ready < replicas.desired {
+            return Ok(Availability::Refused(format!(
+                "{} has {}/{} ready replicas, a previous scale-up or rollout is still in progress",
+                replicas.workload, replicas.ready, replicas.desired
+            )));
+        }
+
+        Ok(Availability::Allowed)
+    }
+
+    async fn replicas(&self, namespace: &str, pod: &Pod) -> Result<Replicas> {
+        match self.workloads.resolve_pod(namespace, pod).await? {
+            Some(workload) => self.workload_replicas(namespace, &workload).await,
+            None => Ok(Replicas {
+                workload: format!("unmanaged Pod {}", pod.name_any()),
+                desired: 1,
+                ready: 1,
+            }),
+        }
+    }
+
+    async fn workload_replicas(&self, namespace: &str, workload: &WorkloadRef) -> Result<Replicas> {
+        let (desired, ready) = match workload.kind.as_str() {
+            "Deployment" => {
+                let deployments: Api<Deployment> =
+                    Api::namespaced(self.kube_client.clone(), namespace);
+                let deployment = deployments.get(&workload.name).await?;
+                (
+                    deployment.spec.and_then(|s| s.replicas).unwrap_or(1),
+                    deployment
+                        .status
+                        .and_then(|s| s.ready_replicas)
+                        .unwrap_or(0),
+                )
+            }
+            "ReplicaSet" => {
+                let replica_sets: Api<ReplicaSet> =
+                    Api::namespaced(self.kube_client.clone(), namespace);
+                let replica_set = replica_sets.get(&workload.name).await?;
+                (
+                    replica_set.spec.and_then(|s| s.replicas).unwrap_or(1),
+                    replica_set
+                        .status
+                        .and_then(|s| s.ready_replicas)
+                        .unwrap_or(0),
+                )
+            }
+            "StatefulSet" => {
+                let stateful_sets: Api<StatefulSet> =
+                    Api::namespaced(self.kube_client.clone(), namespace);
+                let stateful_set = stateful_sets.get(&workload.name).await?;
+                (
+                    stateful_set.spec.and_then(|s| s.replicas).unwrap_or(1),
+                    stateful_set
+                        .status
+                        .and_then(|s| s.ready_replicas)
+                        .unwrap_or(0),
+                )
+            }
+            "DaemonSet" => {
+                let daemon_sets: Api<DaemonSet> =
+                    Api::namespaced(self.kube_client.clone(), namespace);
+                let status = daemon_sets.get(&workload.name).await?.status;
+                (
+                    status
+                        .as_ref()
+                        .map(|s| s.desired_number_scheduled)
+                        .unwrap_or(1),
+                    status.as_ref().map(|s| s.number_ready).unwrap_or(0),
+                )
+            }
+            _ => match self
+                .workloads
+                .api(namespace, workload)
+                .get_scale(&workload.name)
+                .await
+            {
+                Ok(scale) => (
+                    scale.spec.and_then(|s| s.replicas).unwrap_or(1),
+                    scale.status.map(|s| s.replicas).unwrap_or(0),
+                ),
+                Err(e) => {
+                    debug!("{} has no scale subresource: {}", workload, e);
+                    (1, 1)
+                }
+            },
+        };
+
+        Ok(Replicas {
+            workload: workload.to_string(),
+            desired,
+            ready,
+        })
+    }
+}
+
+fn is_ready(pod: &Pod) -> bool {
+    pod.status
+        .as_ref()
+        .and_then(|s| s.conditions.as_ref())
+        .is_some_and(|conditions| {
+            conditions
+                .iter()
+                .any(|c| c.type_ == "Ready" && c.status == "True")
+        })
+}
+
+pub fn selector_matches(selector: &LabelSelector, labels: &BTreeMap<String, String>) -> bool {
+    let match_labels = selector.match_labels.as_ref();
+    let match_expressions = selector.match_expressions.as_ref();
+
+    if match_labels.map_or(true, |l| l.is_empty())
+        && match_expressions.map_or(true, |e| e.is_empty())
+    {
+        return true;
+    }
+
+    let labels_match = match_labels.map_or(true, |match_labels| {
+        match_labels
+            .iter()
+            .all(|(key, value)| labels.get(key) == Some(value))
+    });
+
+    let expressions_match = match_expressions.map_or(true, |expressions| {
+        expressions.iter().all(|expr| {
+            let values = expr.values.as_deref().unwrap_or_default();
+            let value = labels.get(&expr.key);
+            match expr.operator.as_str() {
+                "In" => value.is_some_and(|v| values.contains(v)),
+                "NotIn" => value.map_or(true, |v| !values.contains(v)),
+                "Exists" => value.is_some(),
+                "DoesNotExist" => value.is_none(),
+                _ => false,
+            }
+        })
+    });
+
+    labels_match && expressions_match
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelectorRequirement;
+
+    #[test]
+    fn test_selector_matches() {
+        let labels: BTreeMap<String, String> = [
+            ("app".to_string(), "web".to_string()),
+            ("tier".to_string(), "frontend".to_string()),
+        ]
+        .into_iter()
+        .collect();
+
+        let by_labels = LabelSelector {
+            match_labels: Some([("app".to_string(), "web".to_string())].into()),
+            ..Default::default()
+        };
+        assert!(selector_matches(&by_labels, &labels));
+
+        let by_expression = LabelSelector {
+            match_expressions: Some(vec![LabelSelectorRequirement {
+                key: "tier".to_string(),
+                operator: "NotIn".to_string(),
+                values: Some(vec!["frontend".to_string()]),
+            }]),
+            ..Default::default()
+        };
+        assert!(!selector_matches(&by_expression, &labels));
+
+        assert!(selector_matches(&LabelSelector::default(), &labels));
+    }
+}
@@ -1,35 +1,158 @@
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use k8s_openapi::api::apps::v1::{DaemonSet, Deployment, ReplicaSet, StatefulSet};
+use k8s_openapi::api::core::v1::{Pod, Service};
 use k8s_openapi::api::networking::v1::{
     NetworkPolicy, NetworkPolicyEgressRule, NetworkPolicyIngressRule, NetworkPolicySpec,
 };
-use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
-use kube::api::{DeleteParams, PostParams};
-use kube::{Api, Client as KubeClient};
-use std::collections::HashMap;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, OwnerReference};
+use kube::api::{DeleteParams, ListParams, Patch, PatchParams, PostParams};
+use kube::{Api, Client as KubeClient, Resource, ResourceExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use super::anomaly::AnomalyDetector;
+use super::availability::{selector_matches, Availability, AvailabilityGuard};
+use super::fault_state::{Crossing, FaultStateTracker};
+use super::forecast::TrendForecaster;
+use super::incidents::{Incident, IncidentCorrelator};
+use super::pod_status::{PodStatusFault, PodStatusPoller};
+use super::traffic::TrafficRouter;
 use super::traits::{Agent, EventHandler};
-use crate::clients::PrometheusClient;
+use super::workload::{controller_owner, WorkloadResolver};
+use crate::clients::{PodMetrics, PrometheusClient};
 use crate::crd::{
-    ContainmentConfig, IsolationStrategy, SelfHealingPolicySpec, Thresholds, TriggerReason,
+    AllowedAction, AnomalyScore, ComparisonOperator, ContainmentConfig, HealingEvent,
+    HealingEventSpec, HealingPhase, IsolationStrategy, SelfHealingPolicySpec, SignalTemplate,
+    ThresholdRule, Thresholds, TriggerReason, CORRELATION_ID_LABEL, IGNORE_ANNOTATION,
+    ISOLATED_LABEL, POLICY_NAMESPACE_LABEL, POLICY_NAME_LABEL, QUARANTINE_ANNOTATION,
 };
 use crate::error::{RecistError, Result};
 use crate::eventbus::EventBus;
 use crate::models::{
     AcceptingNeighbor, AgentEvent, AgentEventType, AgentType, Fault, FaultCluster, FaultSeverity,
-    IsolationRule, IsolationRuleType, NeighborNegotiationResult, RejectedNeighbor, TrafficRedirect,
+    IsolationRule, IsolationRuleType, NeighborNegotiationResult, RejectedNeighbor, SignalReading,
+    TrafficRedirect, WorkloadRef,
 };
 
+const SUSTAINED_STEP_SECONDS: u64 = 15;
+const BATCH_QUERY_PODS: usize = 50;
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+struct QuarantineRecord {
+    labels: BTreeMap<String, String>,
+    controller: Option<String>,
+    #[serde(default)]
+    correlation_id: Option<Uuid>,
+    #[serde(default)]
+    quarantined_at: Option<DateTime<Utc>>,
+}
+
+impl QuarantineRecord {
+    fn new(pod: &Pod, selector_keys: &HashSet<String>, correlation_id: Uuid) -> Self {
+        Self {
+            labels: pod
+                .labels()
+                .iter()
+                .filter(|(key, _)| selector_keys.contains(*key))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+            controller: controller_owner(pod).map(|owner| format!("{}/{}", owner.kind, owner.name)),
+            correlation_id: Some(correlation_id),
+            quarantined_at: Some(Utc::now()),
+        }
+    }
+
+    fn from_pod(pod: &Pod) -> Option<Result<Self>> {
+        pod.annotations()
+            .get(QUARANTINE_ANNOTATION)
+            .map(|raw| Ok(serde_json::from_str(raw)?))
+    }
+
+    fn quarantine_patch(&self, isolation_id: &str) -> Result<serde_json::Value> {
+        let mut labels = serde_json::Map::new();
+        for key in self.labels.keys() {
+            labels.insert(key.clone(), serde_json::Value::Null);
+        }
+        labels.insert(
+            ISOLATED_LABEL.to_string(),
+            serde_json::Value::String(isolation_id.to_string()),
+        );
+
+        Ok(serde_json::json!({
+            "metadata": {
+                "labels": labels,
+                "annotations": {
+                    QUARANTINE_ANNOTATION: serde_json::to_string(self)?
+                }
+            }
+        }))
+    }
+
+    fn release_patch(&self) -> serde_json::Value {
+        let mut labels = serde_json::Map::new();
+        for (key, value) in &self.labels {
+            labels.insert(key.clone(), serde_json::Value::String(value.clone()));
+        }
+
+        serde_json::json!({
+            "metadata": {
+                "labels": labels,
+                "annotations": {
+                    QUARANTINE_ANNOTATION: serde_json::Value::Null
+                }
+            }
+        })
+    }
+}
+
+struct Criterion<'a> {
+    key: String,
+    name: &'a str,
+    query: String,
+    value: Option<f64>,
+    operator: ComparisonOperator,
+    threshold: f64,
+    clear_threshold: f64,
+    for_seconds: u64,
+    min_checks: u32,
+}
+
+impl Criterion<'_> {
+    // None when the breach still has to be confirmed as sustained over `for_seconds`.
+    fn breach(&self, crossing: Crossing) -> Option<bool> {
+        match crossing {
+            Crossing::Holding => Some(true),
+            Crossing::Clear | Crossing::Cleared => Some(false),
+            Crossing::Triggering(streak) if streak < self.min_checks => Some(false),
+            Crossing::Triggering(_) if self.for_seconds == 0 => Some(true),
+            Crossing::Triggering(_) => None,
+        }
+    }
+}
+
 pub struct ContainmentAgent {
     kube_client: KubeClient,
+    policy_ref: String,
+    target_selector: String,
+    pod_status: PodStatusPoller,
+    fault_state: FaultStateTracker,
+    anomaly_detector: AnomalyDetector,
+    forecaster: TrendForecaster,
+    incidents: IncidentCorrelator,
+    traffic_router: TrafficRouter,
+    availability: AvailabilityGuard,
+    workloads: WorkloadResolver,
     prometheus: Arc<PrometheusClient>,
     event_bus: EventBus,
     config: ContainmentConfig,
     thresholds: Thresholds,
+    signals: Vec<SignalTemplate>,
+    rules: Vec<ThresholdRule>,
     active_isolations: Arc<RwLock<HashMap<String, IsolationRule>>>,
     running: Arc<RwLock<bool>>,
 }
@@ -38,62 +161,266 @@ impl ContainmentAgent {
     pub async fn new(
         prometheus: Arc<PrometheusClient>,
         event_bus: EventBus,
-        config: ContainmentConfig,
-        thresholds: Thresholds,
+        spec: &SelfHealingPolicySpec,
+        policy_ref: String,
+        cluster_domain: String,
     ) -> Result<Self> {
         let kube_client = KubeClient::try_default()
             .await
             .map_err(|e| RecistError::KubeError(e))?;
 
         Ok(Self {
-            kube_client,
+            kube_client: kube_client.clone(),
+            policy_ref,
+            target_selector: spec.target_selector(),
+            pod_status: PodStatusPoller::new(spec.thresholds.restart_count),
+            fault_state: FaultStateTracker::new(),
+            anomaly_detector: AnomalyDetector::new(
+                prometheus.clone(),
+                spec.containment_config.anomaly_detection.clone(),
+            ),
+            forecaster: TrendForecaster::new(
+                prometheus.clone(),
+                spec.thresholds.clone(),
+                spec.containment_config.forecasting.clone(),
+                spec.containment_config.forecasting.preemptive_scaling
+                    && spec.allowed_actions.contains(&AllowedAction::Scale),
+            ),
+            incidents: IncidentCorrelator::new(spec.containment_config.correlation_window_seconds),
+            traffic_router: TrafficRouter::new(
+                kube_client.clone(),
+                spec.containment_config.traffic_routing,
+                cluster_domain,
+            ),
+            availability: AvailabilityGuard::new(kube_client.clone()),
+            workloads: WorkloadResolver::new(kube_client.clone()),
             prometheus,
             event_bus,
-            config,
-            thresholds,
+            config: spec.containment_config.clone(),
+            thresholds: spec.thresholds.clone(),
+            signals: spec.signals.clone(),
+            rules: spec.rules.clone(),
             active_isolations: Arc::new(RwLock::new(HashMap::new())),
             running: Arc::new(RwLock::new(false)),
         })
     }
 
     pub async fn check_metrics(&self, namespace: &str) -> Result<FaultCluster> {
-        let metrics = self.prometheus.get_all_pod_metrics(namespace).await?;
+        let target_pods = self.resolve_target_pods(namespace).await?;
+        let mut status_faults: HashMap<String, PodStatusFault> = self
+            .pod_status
+            .evaluate(namespace, &target_pods)
+            .await
+            .into_iter()
+            .map(|fault| (fault.pod_name.clone(), fault))
+            .collect();
+
+        let mut metrics: HashMap<String, PodMetrics> =
+            match self.prometheus.get_all_pod_metrics(namespace).await {
+                Ok(metrics) => metrics
+                    .into_iter()
+                    .map(|m| (m.pod_name.clone(), m))
+                    .collect(),
+                Err(e) => {
+                    warn!(
+                        "Failed to get pod metrics for namespace {}, using pod status only: {}",
+                        namespace, e
+                    );
+                    HashMap::new()
+                }
+            };
+
+        let services = match self.service_selectors(namespace).await {
+            Ok(services) => services,
+            Err(e) => {
+                warn!(
+                    "Failed to list Services in namespace {}, faults will not be correlated by Service: {}",
+                    namespace, e
+                );
+                Vec::new()
+            }
+        };
+
+        let pod_names: Vec<String> = target_pods.iter().map(|p| p.name_any()).collect();
+        let target_names: HashSet<String> = pod_names.iter().cloned().collect();
+        self.fault_state.retain_pods(namespace, &target_names).await;
+
+        let (signal_values, rule_values) = tokio::join!(
+            futures::future::join_all(self.signals.iter().map(|template| {
+                self.query_by_pod(namespace, &pod_names, &template.name, |pods| {
+                    template.render_batch(namespace, pods)
+                })
+            })),
+            futures::future::join_all(self.rules.iter().map(|rule| {
+                self.query_by_pod(namespace, &pod_names, &rule.name, |pods| {
+                    rule.render_batch(namespace, pods)
+                })
+            })),
+        );
+
+        let in_healing = match self.pods_in_healing(namespace).await {
+            Ok(pods) => pods,
+            Err(e) => {
+                warn!(
+                    "Failed to list active HealingEvents in namespace {}: {}",
+                    namespace, e
+                );
+                HashSet::new()
+            }
+        };
+
+        let mode = self.config.detection_mode;
+        let workloads: HashMap<String, WorkloadRef> = if mode.uses_anomalies() {
+            futures::future::join_all(pod_names.iter().map(|pod_name| async move {
+                let workload = self.resolve_workload(namespace, pod_name).await?;
+                Some((pod_name.clone(), workload))
+            }))
+            .await
+            .into_iter()
+            .flatten()
+            .collect()
+        } else {
+            HashMap::new()
+        };
+        let mut anomaly_scores = if mode.uses_anomalies() {
+            self.anomaly_detector
+                .detect(namespace, &metrics, &workloads)
+                .await
+        } else {
+            HashMap::new()
+        };
+
         let mut fault_cluster = FaultCluster::new(namespace.to_string());
+        fault_cluster.set_policy_ref(self.policy_ref.clone());
+
+        for pod in &target_pods {
+            let pod_name = pod.name_any();
+            let pod_metrics = metrics.remove(&pod_name);
+            let status_fault = status_faults.remove(&pod_name);
+            let pod_anomalies = self
+                .evaluate_anomalies(
+                    namespace,
+                    &pod_name,
+                    anomaly_scores.remove(&pod_name).unwrap_or_default(),
+                )
+                .await;
 
-        for pod_metrics in metrics {
             let mut reasons = Vec::new();
 
-            if pod_metrics.cpu_usage > self.thresholds.cpu {
-                reasons.push(TriggerReason::HighCpu);
+            if let Some(status_fault) = &status_fault {
+                reasons.extend(status_fault.reasons.iter().cloned());
+            }
+
+            for (reason, _) in &pod_anomalies {
+                if !reasons.contains(reason) {
+                    reasons.push(reason.clone());
+                }
+            }
+
+            if let Some(pod_metrics) = &pod_metrics {
+                if mode.uses_thresholds() {
+                    for reason in self
+                        .evaluate_thresholds(namespace, &pod_name, pod_metrics)
+                        .await
+                    {
+                        if !reasons.contains(&reason) {
+                            reasons.push(reason);
+                        }
+                    }
+                }
+
+                let unknown = pod_metrics.unknown_signals();
+                if !unknown.is_empty() {
+                    debug!(
+                        "No samples for {:?} on pod {}/{}",
+                        unknown, namespace, pod_name
+                    );
+                }
+            } else {
+                debug!("No metrics available for pod {}/{}", namespace, pod_name);
             }
-            if pod_metrics.memory_usage > self.thresholds.memory {
-                reasons.push(TriggerReason::HighMemory);
+
+            let signals = self
+                .evaluate_signals(namespace, &pod_name, &signal_values)
+                .await;
+            for (template, reading) in self.signals.iter().zip(&signals) {
+                let breached = reading.as_ref().is_some_and(|r| r.breached);
+                if breached && !reasons.contains(&template.reason) {
+                    reasons.push(template.reason.clone());
+                }
             }
-            if pod_metrics.error_rate > self.thresholds.error_rate {
-                reasons.push(TriggerReason::HighErrorRate);
+
+            let rule_readings = self
+                .evaluate_rules(namespace, &pod_name, &rule_values)
+                .await;
+            let mut rule_severity: Option<FaultSeverity> = None;
+            for (rule, reading) in self.rules.iter().zip(&rule_readings) {
+                if !reading.as_ref().is_some_and(|r| r.breached) {
+                    continue;
+                }
+                if !reasons.contains(&rule.reason) {
+                    reasons.push(rule.reason.clone());
+                }
+                let severity = FaultSeverity::from(rule.severity);
+                if rule_severity.as_ref().is_none_or(|s| severity > *s) {
+                    rule_severity = Some(severity);
+                }
             }
-            if pod_metrics.latency_ms > self.thresholds.latency_ms as f64 {
-                reasons.push(TriggerReason::HighLatency);
+
+            if !reasons.is_empty() && in_healing.contains(&pod_name) {
+                debug!(
+                    "Pod {}/{} is still breaching {:?} but already has an active HealingEvent",
+                    namespace, pod_name, reasons
+                );
+                continue;
             }
 
             if !reasons.is_empty() {
-                let fault = Fault::new(
-                    pod_metrics.pod_name.clone(),
+                let mut fault = Fault::new(
+                    pod_name.clone(),
                     namespace.to_string(),
                     reasons,
                     crate::crd::TriggerMetrics {
-                        cpu_usage: Some(pod_metrics.cpu_usage),
-                        memory_usage: Some(pod_metrics.memory_usage),
-                        error_rate: Some(pod_metrics.error_rate),
-                        latency_ms: Some(pod_metrics.latency_ms as u64),
-                        restart_count: None,
+                        cpu_usage: pod_metrics.as_ref().and_then(|m| m.cpu_usage),
+                        memory_usage: pod_metrics.as_ref().and_then(|m| m.memory_usage),
+                        error_rate: pod_metrics.as_ref().and_then(|m| m.error_rate),
+                        latency_ms: pod_metrics
+                            .as_ref()
+                            .and_then(|m| m.latency_ms)
+                            .map(|v| v as u64),
+                        restart_count: Some(container_restarts(pod)),
+                        anomalies: pod_anomalies.into_iter().map(|(_, score)| score).collect(),
                     },
                 );
 
+                fault.set_signals(signals.into_iter().chain(rule_readings).flatten().collect());
+                if let Some(severity) = rule_severity {
+                    fault.escalate(severity);
+                }
+
+                let workload = match workloads.get(&pod_name) {
+                    Some(workload) => Some(workload.clone()),
+                    None => self.resolve_workload(namespace, &pod_name).await,
+                };
+                if let Some(workload) = workload {
+                    fault.set_workload(workload);
+                }
+                fault.set_placement(
+                    pod.spec.as_ref().and_then(|spec| spec.node_name.clone()),
+                    selecting(&services, pod.labels())
+                        .map(|(name, _)| name.clone())
+                        .collect(),
+                );
+
                 info!(
                     "Fault detected in pod {}/{}: {:?}",
-                    namespace, pod_metrics.pod_name, fault.reasons
+                    namespace, pod_name, fault.reasons
                 );
+                if let Some(status_fault) = &status_fault {
+                    for detail in &status_fault.details {
+                        debug!("Pod {}/{}: {}", namespace, pod_name, detail);
+                    }
+                }
 
                 fault_cluster.add_fault(fault);
             }
@@ -102,43 +429,534 @@ impl ContainmentAgent {
         Ok(fault_cluster)
     }
 
-    pub async fn isolate_pod(&self, fault: &Fault) -> Result<IsolationRule> {
-        let strategy = self.determine_isolation_strategy(fault);
-        let policy_name = format!("recist-isolate-{}", fault.pod_name);
+    async fn evaluate_thresholds(
+        &self,
+        namespace: &str,
+        pod_name: &str,
+        pod_metrics: &PodMetrics,
+    ) -> Vec<TriggerReason> {
+        let builtins = [
+            (
+                TriggerReason::HighCpu,
+                "cpu",
+                pod_metrics.cpu_usage,
+                self.thresholds.cpu,
+                PrometheusClient::cpu_usage_query(namespace, pod_name),
+            ),
+            (
+                TriggerReason::HighMemory,
+                "memory",
+                pod_metrics.memory_usage,
+                self.thresholds.memory,
+                PrometheusClient::memory_usage_query(namespace, pod_name),
+            ),
+            (
+                TriggerReason::HighErrorRate,
+                "errorRate",
+                pod_metrics.error_rate,
+                self.thresholds.error_rate,
+                PrometheusClient::error_rate_query(namespace, pod_name),
+            ),
+            (
+                TriggerReason::HighLatency,
+                "latency",
+                pod_metrics.latency_ms,
+                self.thresholds.latency_ms as f64,
+                PrometheusClient::latency_p99_query(namespace, pod_name),
+            ),
+        ];
+
+        let mut reasons = Vec::new();
+        for (reason, name, value, threshold, query) in builtins {
+            let criterion = Criterion {
+                key: format!("threshold/{}", name),
+                name,
+                query,
+                value,
+                operator: ComparisonOperator::GreaterThan,
+                threshold,
+                clear_threshold: self.thresholds.clear_value(threshold),
+                for_seconds: self.thresholds.for_seconds,
+                min_checks: 1,
+            };
+            let breached = self
+                .assess(namespace, pod_name, criterion)
+                .await
+                .is_some_and(|r| r.breached);
+            if breached {
+                reasons.push(reason);
+            }
+        }
+
+        reasons
+    }
+
+    async fn evaluate_anomalies(
+        &self,
+        namespace: &str,
+        pod_name: &str,
+        scores: Vec<(TriggerReason, AnomalyScore)>,
+    ) -> Vec<(TriggerReason, AnomalyScore)> {
+        let config = &self.config.anomaly_detection;
+        let mut anomalies = Vec::new();
+
+        for (reason, score) in scores {
+            let criterion = Criterion {
+                key: format!("anomaly/{}", score.metric),
+                name: &score.metric,
+                query: String::new(),
+                value: Some(score.z_score),
+                operator: ComparisonOperator::GreaterThanOrEqual,
+                threshold: config.z_score_threshold,
+                clear_threshold: self.thresholds.clear_value(config.z_score_threshold),
+                for_seconds: 0,
+                min_checks: config.consecutive_checks,
+            };
+            let breached = self
+                .assess(namespace, pod_name, criterion)
+                .await
+                .is_some_and(|r| r.breached);
+            if breached {
+                anomalies.push((reason, score));
+            }
+        }
+
+        anomalies
+    }
+
+    async fn query_by_pod(
+        &self,
+        namespace: &str,
+        pods: &[String],
+        name: &str,
+        render: impl Fn(&[String]) -> Option<String>,
+    ) -> Option<HashMap<String, f64>> {
+        let mut values = HashMap::new();
+
+        for chunk in pods.chunks(BATCH_QUERY_PODS) {
+            let query = render(chunk)?;
+            match self.prometheus.query_instant(&query).await {
+                Ok(samples) => values.extend(
+                    samples
+                        .into_iter()
+                        .filter(|s| s.value.is_finite())
+                        .filter_map(|s| Some((s.labels.get("pod")?.clone(), s.value))),
+                ),
+                Err(e) => warn!(
+                    "Failed to evaluate {} for namespace {}: {}",
+                    name, namespace, e
+                ),
+            }
+        }
+
+        Some(values)
+    }
+
+    async fn evaluate_signals(
+        &self,
+        namespace: &str,
+        pod_name: &str,
+        batched: &[Option<HashMap<String, f64>>],
+    ) -> Vec<Option<SignalReading>> {
+        let queries = self.signals.iter().zip(batched).map(|(template, values)| {
+            let criterion = Criterion {
+                key: format!("signal/{}", template.name),
+                name: &template.name,
+                query: template.render(namespace, pod_name),
+                value: values.as_ref().and_then(|v| v.get(pod_name).copied()),
+                operator: template.operator,
+                threshold: template.threshold,
+                clear_threshold: template.clear_threshold.unwrap_or(template.threshold),
+                for_seconds: template.for_seconds,
+                min_checks: 1,
+            };
+            self.assess_batched(namespace, pod_name, values.is_some(), criterion)
+        });
+
+        futures::future::join_all(queries).await
+    }
+
+    async fn evaluate_rules(
+        &self,
+        namespace: &str,
+        pod_name: &str,
+        batched: &[Option<HashMap<String, f64>>],
+    ) -> Vec<Option<SignalReading>> {
+        let queries = self.rules.iter().zip(batched).map(|(rule, values)| {
+            let criterion = Criterion {
+                key: format!("rule/{}", rule.name),
+                name: &rule.name,
+                query: rule.render(namespace, pod_name),
+                value: values.as_ref().and_then(|v| v.get(pod_name).copied()),
+                operator: rule.operator,
+                threshold: rule.value,
+                clear_threshold: rule.clear_value.unwrap_or(rule.value),
+                for_seconds: rule.for_seconds,
+                min_checks: 1,
+            };
+            self.assess_batched(namespace, pod_name, values.is_some(), criterion)
+        });
+
+        futures::future::join_all(queries).await
+    }
+
+    async fn assess_batched(
+        &self,
+        namespace: &str,
+        pod_name: &str,
+        batched: bool,
+        criterion: Criterion<'_>,
+    ) -> Option<SignalReading> {
+        if batched && criterion.value.is_none() {
+            debug!(
+                "{} returned no samples for pod {}/{}",
+                criterion.name, namespace, pod_name
+            );
+            return None;
+        }
+
+        self.assess(namespace, pod_name, criterion).await
+    }
+
+    async fn assess(
+        &self,
+        namespace: &str,
+        pod_name: &str,
+        criterion: Criterion<'_>,
+    ) -> Option<SignalReading> {
+        let value = match criterion.value {
+            Some(value) => Some(value),
+            None => match self.prometheus.query_value(&criterion.query).await {
+                Ok(value) => value,
+                Err(e) => {
+                    warn!(
+                        "Failed to evaluate {} for pod {}/{}: {}",
+                        criterion.name, namespace, pod_name, e
+                    );
+                    None
+                }
+            },
+        };
+
+        let value = match value {
+            Some(value) => value,
+            None => {
+                debug!(
+                    "{} returned no samples for pod {}/{}",
+                    criterion.name, namespace, pod_name
+                );
+                return None;
+            }
+        };
+
+        let crossing = self
+            .fault_state
+            .observe(
+                namespace,
+                pod_name,
+                &criterion.key,
+                criterion.operator.compare(value, criterion.threshold),
+                criterion.operator.compare(value, criterion.clear_threshold),
+            )
+            .await;
+
+        let breached = match criterion.breach(crossing) {
+            Some(breached) => breached,
+            None => self.is_sustained(&criterion).await.unwrap_or_else(|e| {
+                warn!(
+                    "Failed to check sustained breach of {} for pod {}/{}: {}",
+                    criterion.name, namespace, pod_name, e
+                );
+                false
+            }),
+        };
+
+        let was_active = crossing.was_active();
+        if breached != was_active {
+            debug!(
+                "{} for pod {}/{} is now {} ({} {} {})",
+                criterion.name,
+                namespace,
+                pod_name,
+                if breached { "breaching" } else { "clear" },
+                value,
+                criterion.operator,
+                if was_active {
+                    criterion.clear_threshold
+                } else {
+                    criterion.threshold
+                }
+            );
+        }
+        if breached && !was_active {
+            self.fault_state
+                .set_active(namespace, pod_name, &criterion.key, true)
+                .await;
+        }
+
+        Some(SignalReading {
+            name: criterion.name.to_string(),
+            value,
+            operator: criterion.operator,
+            threshold: criterion.threshold,
+            breached,
+        })
+    }
+
+    async fn is_sustained(&self, criterion: &Criterion<'_>) -> Result<bool> {
+        let end = Utc::now();
+        let start = end - ChronoDuration::seconds(criterion.for_seconds as i64);
+        let step = SUSTAINED_STEP_SECONDS.min(criterion.for_seconds).max(1);
+
+        let series = self
+            .prometheus
+            .query_range(&criterion.query, start, end, step)
+            .await?;
+
+        let values: Vec<(DateTime<Utc>, f64)> = series
+            .into_iter()
+            .next()
+            .map(|s| s.values)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(_, v)| v.is_finite())
+            .collect();
+
+        let covers_window = values
+            .first()
+            .is_some_and(|(first_at, _)| *first_at <= start + ChronoDuration::seconds(step as i64));
+
+        Ok(covers_window
+            && values
+                .iter()
+                .all(|(_, value)| criterion.operator.compare(*value, criterion.threshold)))
+    }
+
+    async fn pods_in_healing(&self, namespace: &str) -> Result<HashSet<String>> {
+        let (policy_namespace, policy_name) = self
+            .policy_ref
+            .split_once('/')
+            .unwrap_or(("", self.policy_ref.as_str()));
+        let selector = format!(
+            "{}={},{}={}",
+            POLICY_NAME_LABEL, policy_name, POLICY_NAMESPACE_LABEL, policy_namespace
+        );
+
+        let api: Api<HealingEvent> = Api::namespaced(self.kube_client.clone(), namespace);
+        let events = api.list(&ListParams::default().labels(&selector)).await?;
+
+        Ok(events
+            .items
+            .into_iter()
+            .filter(|event| {
+                !matches!(
+                    event.status.as_ref().map(|s| &s.phase),
+                    Some(HealingPhase::Completed) | Some(HealingPhase::Failed)
+                )
+            })
+            .map(|event| event.spec.target_pod)
+            .collect())
+    }
+
+    pub async fn resolve_target_pods(&self, namespace: &str) -> Result<Vec<Pod>> {
+        let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
+        let mut params = ListParams::default();
+        if !self.target_selector.is_empty() {
+            params = params.labels(&self.target_selector);
+        }
+
+        let pods = pods.list(&params).await?;
+        let ignored_workloads = self.ignored_workloads(namespace).await?;
+
+        let targets: Vec<Pod> = pods
+            .items
+            .into_iter()
+            .filter(|pod| !is_ignored(pod))
+            .filter(|pod| {
+                controller_owner(pod)
+                    .map(|owner| !ignored_workloads.contains(&(owner.kind, owner.name)))
+                    .unwrap_or(true)
+            })
+            .collect();
+
+        debug!(
+            "Resolved {} target pods in namespace {} with selector '{}'",
+            targets.len(),
+            namespace,
+            self.target_selector
+        );
+
+        Ok(targets)
+    }
+
+    async fn ignored_workloads(&self, namespace: &str) -> Result<HashSet<(String, String)>> {
+        let mut ignored = HashSet::new();
+        let params = ListParams::default();
+
+        let deployments: Api<Deployment> = Api::namespaced(self.kube_client.clone(), namespace);
+        for deployment in deployments.list(&params).await?.items {
+            if is_ignored(&deployment) {
+                ignored.insert(("Deployment".to_string(), deployment.name_any()));
+            }
+        }
+
+        let stateful_sets: Api<StatefulSet> = Api::namespaced(self.kube_client.clone(), namespace);
+        for stateful_set in stateful_sets.list(&params).await?.items {
+            if is_ignored(&stateful_set) {
+                ignored.insert(("StatefulSet".to_string(), stateful_set.name_any()));
+            }
+        }
+
+        let daemon_sets: Api<DaemonSet> = Api::namespaced(self.kube_client.clone(), namespace);
+        for daemon_set in daemon_sets.list(&params).await?.items {
+            if is_ignored(&daemon_set) {
+                ignored.insert(("DaemonSet".to_string(), daemon_set.name_any()));
+            }
+        }
+
+        let replica_sets: Api<ReplicaSet> = Api::namespaced(self.kube_client.clone(), namespace);
+        for replica_set in replica_sets.list(&params).await?.items {
+            let owner_ignored = controller_owner(&replica_set)
+                .map(|owner| ignored.contains(&(owner.kind, owner.name)))
+                .unwrap_or(false);
+            if owner_ignored || is_ignored(&replica_set) {
+                ignored.insert(("ReplicaSet".to_string(), replica_set.name_any()));
+            }
+        }
+
+        Ok(ignored)
+    }
+
+    async fn resolve_workload(&self, namespace: &str, pod_name: &str) -> Option<WorkloadRef> {
+        match self.workloads.resolve(namespace, pod_name).await {
+            Ok(workload) => workload,
+            Err(e) => {
+                debug!(
+                    "Failed to resolve workload of pod {}/{}: {}",
+                    namespace, pod_name, e
+                );
+                None
+            }
+        }
+    }
+
+    pub async fn create_healing_event(
+        &self,
+        correlation_id: Uuid,
+        fault: &Fault,
+    ) -> Result<HealingEvent> {
+        let name = format!(
+            "{}-{}",
+            fault.pod_name,
+            &correlation_id.simple().to_string()[..8]
+        );
+
+        let (policy_namespace, policy_name) = self
+            .policy_ref
+            .split_once('/')
+            .unwrap_or(("", self.policy_ref.as_str()));
+
+        let mut labels = std::collections::BTreeMap::new();
+        labels.insert(CORRELATION_ID_LABEL.to_string(), correlation_id.to_string());
+        labels.insert(POLICY_NAME_LABEL.to_string(), policy_name.to_string());
+        labels.insert(
+            POLICY_NAMESPACE_LABEL.to_string(),
+            policy_namespace.to_string(),
+        );
+
+        let mut healing_event = HealingEvent::new(
+            &name,
+            HealingEventSpec {
+                policy_ref: self.policy_ref.clone(),
+                target_pod: fault.pod_name.clone(),
+                target_namespace: fault.namespace.clone(),
+                trigger_reason: fault.primary_reason().clone(),
+                trigger_metrics: Some(fault.metrics.clone()),
+            },
+        );
+        healing_event.metadata.namespace = Some(fault.namespace.clone());
+        healing_event.metadata.labels = Some(labels);
+
+        let api: Api<HealingEvent> = Api::namespaced(self.kube_client.clone(), &fault.namespace);
+        let created = api.create(&PostParams::default(), &healing_event).await?;
+
+        info!(
+            "Created HealingEvent {}/{} for pod {} (correlation {})",
+            fault.namespace, name, fault.pod_name, correlation_id
+        );
+
+        Ok(created)
+    }
+
+    pub async fn isolate_pod(
+        &self,
+        fault: &Fault,
+        correlation_id: Uuid,
+        healing_event: Option<&HealingEvent>,
+    ) -> Result<IsolationRule> {
+        let isolation_id = Uuid::new_v4().simple().to_string();
+        let availability = self
+            .availability
+            .assess(&fault.namespace, &fault.pod_name)
+            .await?;
+        let (strategy, downgrade_reason) =
+            guard_strategy(self.determine_isolation_strategy(fault), availability)?;
+        if let Some(reason) = &downgrade_reason {
+            warn!("Pod {}/{}: {}", fault.namespace, fault.pod_name, reason);
+        }
 
         info!(
             "Isolating pod {}/{} with strategy {:?}",
             fault.namespace, fault.pod_name, strategy
         );
 
-        let network_policy = self.create_network_policy(&policy_name, &fault.pod_name, &strategy);
-        let api: Api<NetworkPolicy> = Api::namespaced(self.kube_client.clone(), &fault.namespace);
+        let services = if self.traffic_router.enabled() {
+            let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), &fault.namespace);
+            let pod = pods.get(&fault.pod_name).await?;
+            self.selecting_services(&fault.namespace, pod.labels())
+                .await?
+        } else {
+            Vec::new()
+        };
 
-        match api.create(&PostParams::default(), &network_policy).await {
-            Ok(_) => {
-                info!(
-                    "Created NetworkPolicy {} for pod {}",
-                    policy_name, fault.pod_name
-                );
+        let (network_policy_name, rule_type) = match strategy {
+            IsolationStrategy::Quarantine => {
+                self.quarantine_pod(
+                    &fault.namespace,
+                    &fault.pod_name,
+                    &isolation_id,
+                    correlation_id,
+                )
+                .await?;
+                (String::new(), IsolationRuleType::ServiceQuarantine)
             }
-            Err(kube::Error::Api(ae)) if ae.code == 409 => {
-                debug!("NetworkPolicy {} already exists, updating", policy_name);
-                let _ = api.delete(&policy_name, &DeleteParams::default()).await;
-                api.create(&PostParams::default(), &network_policy).await?;
+            _ => {
+                let owner = healing_event.and_then(|event| event.controller_owner_ref(&()));
+                let policy_name = self
+                    .apply_network_policy(fault, &isolation_id, &strategy, correlation_id, owner)
+                    .await?;
+                let rule_type = match strategy {
+                    IsolationStrategy::Hard => IsolationRuleType::DenyAll,
+                    _ => IsolationRuleType::DenyIngress,
+                };
+                (policy_name, rule_type)
             }
-            Err(e) => return Err(RecistError::KubeError(e)),
-        }
+        };
+
+        let redirects = self
+            .redirect_traffic(fault, &isolation_id, correlation_id, &services)
+            .await;
 
         let rule = IsolationRule {
             pod_name: fault.pod_name.clone(),
             namespace: fault.namespace.clone(),
-            network_policy_name: policy_name,
+            network_policy_name,
+            isolation_id,
+            correlation_id: Some(correlation_id),
             created_at: Utc::now(),
-            rule_type: match strategy {
-                IsolationStrategy::Hard => IsolationRuleType::DenyAll,
-                IsolationStrategy::Soft => IsolationRuleType::DenyIngress,
-                IsolationStrategy::Auto => IsolationRuleType::DenyIngress,
-            },
+            rule_type,
+            redirects,
+            downgrade_reason,
         };
 
         let mut isolations = self.active_isolations.write().await;
@@ -147,13 +965,147 @@ impl ContainmentAgent {
         Ok(rule)
     }
 
-    pub async fn remove_isolation(&self, pod_name: &str, namespace: &str) -> Result<()> {
-        let policy_name = format!("recist-isolate-{}", pod_name);
+    async fn redirect_traffic(
+        &self,
+        fault: &Fault,
+        isolation_id: &str,
+        correlation_id: Uuid,
+        services: &[(String, BTreeMap<String, String>)],
+    ) -> Vec<TrafficRedirect> {
+        let mut redirects = Vec::new();
+
+        for (service, selector) in services {
+            let negotiation = match self
+                .negotiate_with_neighbors(&fault.pod_name, &fault.namespace, selector)
+                .await
+            {
+                Ok(negotiation) => negotiation,
+                Err(e) => {
+                    warn!(
+                        "Failed to negotiate with neighbors of pod {} behind Service {}: {}",
+                        fault.pod_name, service, e
+                    );
+                    continue;
+                }
+            };
+
+            if negotiation.accepting_pods.is_empty() {
+                warn!(
+                    "No neighbor behind Service {}/{} can absorb traffic from pod {}",
+                    fault.namespace, service, fault.pod_name
+                );
+                continue;
+            }
+
+            match self
+                .traffic_router
+                .redirect(
+                    &fault.namespace,
+                    service,
+                    &fault.pod_name,
+                    isolation_id,
+                    correlation_id,
+                    &negotiation.accepting_pods,
+                )
+                .await
+            {
+                Ok(redirect) => redirects.push(redirect),
+                Err(e) => warn!(
+                    "Failed to redirect traffic for Service {}/{}: {}",
+                    fault.namespace, service, e
+                ),
+            }
+        }
+
+        redirects
+    }
+
+    async fn apply_network_policy(
+        &self,
+        fault: &Fault,
+        isolation_id: &str,
+        strategy: &IsolationStrategy,
+        correlation_id: Uuid,
+        owner: Option<OwnerReference>,
+    ) -> Result<String> {
+        let policy_name = format!("recist-isolate-{}", fault.pod_name);
+
+        self.label_pod(
+            &fault.namespace,
+            &fault.pod_name,
+            serde_json::Value::String(isolation_id.to_string()),
+        )
+        .await?;
+
+        let network_policy =
+            self.create_network_policy(&policy_name, isolation_id, strategy, correlation_id, owner);
+
+        let applied = async {
+            self.replace_network_policy(&fault.namespace, &network_policy)
+                .await?;
+            self.verify_isolation(&fault.namespace, &fault.pod_name, &policy_name)
+                .await
+        }
+        .await;
+
+        if let Err(e) = applied {
+            warn!(
+                "Isolation of pod {}/{} failed, removing its NetworkPolicy and label: {}",
+                fault.namespace, fault.pod_name, e
+            );
+            if let Err(cleanup) = self
+                .delete_network_policy(&fault.namespace, &policy_name)
+                .await
+            {
+                error!(
+                    "Failed to remove NetworkPolicy {}/{}: {}",
+                    fault.namespace, policy_name, cleanup
+                );
+            }
+            if let Err(cleanup) = self
+                .label_pod(&fault.namespace, &fault.pod_name, serde_json::Value::Null)
+                .await
+            {
+                error!(
+                    "Failed to remove isolation label from pod {}/{}: {}",
+                    fault.namespace, fault.pod_name, cleanup
+                );
+            }
+            return Err(e);
+        }
+
+        Ok(policy_name)
+    }
+
+    async fn replace_network_policy(
+        &self,
+        namespace: &str,
+        network_policy: &NetworkPolicy,
+    ) -> Result<()> {
+        let policy_name = network_policy.name_any();
+        let api: Api<NetworkPolicy> = Api::namespaced(self.kube_client.clone(), namespace);
+
+        match api.create(&PostParams::default(), network_policy).await {
+            Ok(_) => {
+                info!("Created NetworkPolicy {}/{}", namespace, policy_name);
+            }
+            Err(kube::Error::Api(ae)) if ae.code == 409 => {
+                debug!("NetworkPolicy {} already exists, updating", policy_name);
+                self.delete_network_policy(namespace, &policy_name).await?;
+                api.create(&PostParams::default(), network_policy).await?;
+            }
+            Err(e) => return Err(RecistError::KubeError(e)),
+        }
+
+        Ok(())
+    }
+
+    async fn delete_network_policy(&self, namespace: &str, policy_name: &str) -> Result<()> {
         let api: Api<NetworkPolicy> = Api::namespaced(self.kube_client.clone(), namespace);
 
-        match api.delete(&policy_name, &DeleteParams::default()).await {
+        match api.delete(policy_name, &DeleteParams::default()).await {
             Ok(_) => {
-                info!("Removed NetworkPolicy {} for pod {}", policy_name, pod_name);
+                info!("Removed NetworkPolicy {}/{}", namespace, policy_name);
             }
             Err(kube::Error::Api(ae)) if ae.code == 404 => {
                 debug!("NetworkPolicy {} not found, already removed", policy_name);
@@ -161,28 +1113,416 @@ impl ContainmentAgent {
             Err(e) => return Err(RecistError::KubeError(e)),
         }
 
+        Ok(())
+    }
+
+    async fn quarantine_pod(
+        &self,
+        namespace: &str,
+        pod_name: &str,
+        isolation_id: &str,
+        correlation_id: Uuid,
+    ) -> Result<()> {
+        let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
+        let pod = pods.get(pod_name).await?;
+        let pod_labels = pod.labels().clone();
+
+        let selector_keys: HashSet<String> = self
+            .selecting_services(namespace, &pod_labels)
+            .await?
+            .into_iter()
+            .flat_map(|(_, selector)| selector.into_keys())
+            .collect();
+
+        if selector_keys.is_empty() {
+            info!(
+                "Pod {}/{} is not selected by any Service, nothing to drain",
+                namespace, pod_name
+            );
+        }
+
+        let record = QuarantineRecord::new(&pod, &selector_keys, correlation_id);
+        let patch = record.quarantine_patch(isolation_id)?;
+        pods.patch(pod_name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await?;
+
+        let quarantined = pods.get(pod_name).await?;
+        let still_selected = self
+            .selecting_services(namespace, quarantined.labels())
+            .await?;
+        if !still_selected.is_empty() {
+            return Err(RecistError::ContainmentError(format!(
+                "Pod {}/{} is still selected by Services {:?} after quarantine",
+                namespace,
+                pod_name,
+                still_selected
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .collect::<Vec<_>>()
+            )));
+        }
+
+        info!(
+            "Quarantined pod {}/{} out of Service endpoints by removing labels {:?}",
+            namespace,
+            pod_name,
+            record.labels.keys().collect::<Vec<_>>()
+        );
+
+        Ok(())
+    }
+
+    async fn release_quarantine(&self, namespace: &str, pod: &Pod) -> Result<()> {
+        let pod_name = pod.name_any();
+        let record = match QuarantineRecord::from_pod(pod) {
+            Some(record) => record?,
+            None => return Ok(()),
+        };
+
+        let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
+
+        if record.controller.is_some() && controller_owner(pod).is_none() {
+            info!(
+                "Quarantined pod {}/{} was released by {} and already replaced, deleting it",
+                namespace,
+                pod_name,
+                record.controller.as_deref().unwrap_or_default()
+            );
+            pods.delete(&pod_name, &DeleteParams::default()).await?;
+            return Ok(());
+        }
+
+        let patch = record.release_patch();
+        pods.patch(&pod_name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await?;
+
+        info!(
+            "Restored Service selector labels on pod {}/{}",
+            namespace, pod_name
+        );
+
+        Ok(())
+    }
+
+    async fn selecting_services(
+        &self,
+        namespace: &str,
+        pod_labels: &BTreeMap<String, String>,
+    ) -> Result<Vec<(String, BTreeMap<String, String>)>> {
+        let services = self.service_selectors(namespace).await?;
+        Ok(selecting(&services, pod_labels).cloned().collect())
+    }
+
+    async fn service_selectors(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<(String, BTreeMap<String, String>)>> {
+        let services: Api<Service> = Api::namespaced(self.kube_client.clone(), namespace);
+
+        Ok(services
+            .list(&ListParams::default())
+            .await?
+            .items
+            .into_iter()
+            .filter_map(|service| {
+                let selector = service.spec.as_ref()?.selector.clone()?;
+                // A Service with an empty selector has its endpoints managed externally.
+                (!selector.is_empty()).then(|| (service.name_any(), selector))
+            })
+            .collect())
+    }
+
+    async fn label_pod(
+        &self,
+        namespace: &str,
+        pod_name: &str,
+        isolation_id: serde_json::Value,
+    ) -> Result<()> {
+        let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
+        let patch = serde_json::json!({
+            "metadata": {
+                "labels": {
+                    ISOLATED_LABEL: isolation_id
+                }
+            }
+        });
+
+        pods.patch(pod_name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn verify_isolation(
+        &self,
+        namespace: &str,
+        pod_name: &str,
+        policy_name: &str,
+    ) -> Result<()> {
+        let policies: Api<NetworkPolicy> = Api::namespaced(self.kube_client.clone(), namespace);
+        let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
+
+        let policy = policies.get(policy_name).await?;
+        let pod = pods.get(pod_name).await?;
+
+        let selector = policy
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.pod_selector.match_labels.clone())
+            .unwrap_or_default();
+        let pod_labels = pod.labels();
+
+        let matches = !selector.is_empty()
+            && selector
+                .iter()
+                .all(|(key, value)| pod_labels.get(key) == Some(value));
+
+        if !matches {
+            return Err(RecistError::ContainmentError(format!(
+                "NetworkPolicy {}/{} does not select pod {}",
+                namespace, policy_name, pod_name
+            )));
+        }
+
+        debug!(
+            "Verified NetworkPolicy {}/{} selects pod {}",
+            namespace, policy_name, pod_name
+        );
+
+        Ok(())
+    }
+
+    pub async fn remove_isolation(&self, pod_name: &str, namespace: &str) -> Result<()> {
+        let mut errors = Vec::new();
+
+        let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
+        let pod = match pods.get_opt(pod_name).await {
+            Ok(pod) => pod,
+            Err(e) => {
+                errors.push(format!("failed to get pod: {}", e));
+                None
+            }
+        };
+
+        if self.traffic_router.enabled() {
+            let isolation_id = match self.active_isolations.read().await.get(pod_name) {
+                Some(rule) => Some(rule.isolation_id.clone()),
+                None => pod
+                    .as_ref()
+                    .and_then(|pod| pod.labels().get(ISOLATED_LABEL).cloned()),
+            };
+            if let Some(isolation_id) = isolation_id.filter(|id| !id.is_empty()) {
+                if let Err(e) = self.traffic_router.revert(namespace, &isolation_id).await {
+                    errors.push(format!("failed to revert traffic redirects: {}", e));
+                }
+            }
+        }
+
+        let policy_name = format!("recist-isolate-{}", pod_name);
+        if let Err(e) = self.delete_network_policy(namespace, &policy_name).await {
+            errors.push(format!(
+                "failed to delete NetworkPolicy {}: {}",
+                policy_name, e
+            ));
+        }
+
+        if let Some(pod) = &pod {
+            if let Err(e) = self.release_quarantine(namespace, pod).await {
+                errors.push(format!("failed to release quarantine: {}", e));
+            }
+        }
+
+        match self
+            .label_pod(namespace, pod_name, serde_json::Value::Null)
+            .await
+        {
+            Ok(()) => debug!(
+                "Removed isolation label from pod {}/{}",
+                namespace, pod_name
+            ),
+            Err(RecistError::KubeError(kube::Error::Api(ae))) if ae.code == 404 => {
+                debug!("Pod {}/{} no longer exists", namespace, pod_name);
+            }
+            Err(e) => errors.push(format!("failed to remove isolation label: {}", e)),
+        }
+
+        if !errors.is_empty() {
+            return Err(RecistError::ContainmentError(format!(
+                "Isolation of pod {}/{} was only partly removed: {}",
+                namespace,
+                pod_name,
+                errors.join("; ")
+            )));
+        }
+
         let mut isolations = self.active_isolations.write().await;
         isolations.remove(pod_name);
 
         Ok(())
     }
 
+    pub async fn restore_isolations(&self, namespaces: &[String]) {
+        let mut restored = Vec::new();
+
+        for namespace in namespaces {
+            match self.list_isolations(namespace).await {
+                Ok(rules) => restored.extend(rules),
+                Err(e) => warn!(
+                    "Failed to restore isolations in namespace {}: {}",
+                    namespace, e
+                ),
+            }
+        }
+
+        let mut isolations = self.active_isolations.write().await;
+        for rule in restored {
+            info!(
+                "Restored {} isolation for pod {}/{}",
+                rule.rule_type.method(),
+                rule.namespace,
+                rule.pod_name
+            );
+            isolations.entry(rule.pod_name.clone()).or_insert(rule);
+        }
+    }
+
+    async fn list_isolations(&self, namespace: &str) -> Result<Vec<IsolationRule>> {
+        let mut rules = Vec::new();
+
+        let policies: Api<NetworkPolicy> = Api::namespaced(self.kube_client.clone(), namespace);
+        let params = ListParams::default().labels(&format!(
+            "app.kubernetes.io/managed-by=recist,{}",
+            ISOLATED_LABEL
+        ));
+        for policy in policies.list(&params).await?.items {
+            let policy_name = policy.name_any();
+            let pod_name = match policy_name.strip_prefix("recist-isolate-") {
+                Some(pod_name) => pod_name.to_string(),
+                None => continue,
+            };
+            let labels = policy.labels();
+            let denies_egress = policy
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.policy_types.as_ref())
+                .map(|types| types.iter().any(|t| t == "Egress"))
+                .unwrap_or(false);
+
+            rules.push(IsolationRule {
+                pod_name,
+                namespace: namespace.to_string(),
+                isolation_id: labels.get(ISOLATED_LABEL).cloned().unwrap_or_default(),
+                correlation_id: labels
+                    .get(CORRELATION_ID_LABEL)
+                    .and_then(|id| Uuid::parse_str(id).ok()),
+                created_at: policy
+                    .creation_timestamp()
+                    .map(|t| t.0)
+                    .unwrap_or_else(Utc::now),
+                rule_type: if denies_egress {
+                    IsolationRuleType::DenyAll
+                } else {
+                    IsolationRuleType::DenyIngress
+                },
+                network_policy_name: policy_name,
+                redirects: Vec::new(),
+                downgrade_reason: None,
+            });
+        }
+
+        let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
+        let params = ListParams::default().labels(ISOLATED_LABEL);
+        for pod in pods.list(&params).await?.items {
+            let record = match QuarantineRecord::from_pod(&pod) {
+                Some(Ok(record)) => record,
+                _ => continue,
+            };
+
+            rules.push(IsolationRule {
+                pod_name: pod.name_any(),
+                namespace: namespace.to_string(),
+                network_policy_name: String::new(),
+                isolation_id: pod
+                    .labels()
+                    .get(ISOLATED_LABEL)
+                    .cloned()
+                    .unwrap_or_default(),
+                correlation_id: record.correlation_id,
+                created_at: record.quarantined_at.unwrap_or_else(Utc::now),
+                rule_type: IsolationRuleType::ServiceQuarantine,
+                redirects: Vec::new(),
+                downgrade_reason: None,
+            });
+        }
+
+        Ok(rules)
+    }
+
+    pub async fn expire_isolations(&self) {
+        if self.config.max_isolation_minutes == 0 {
+            return;
+        }
+
+        let cutoff = Utc::now() - ChronoDuration::minutes(self.config.max_isolation_minutes as i64);
+        let expired = expired_isolations(self.active_isolations.read().await.values(), cutoff);
+
+        for (pod_name, namespace) in expired {
+            info!(
+                "Isolation of pod {}/{} exceeded {} minutes, removing it",
+                namespace, pod_name, self.config.max_isolation_minutes
+            );
+            if let Err(e) = self.remove_isolation(&pod_name, &namespace).await {
+                warn!(
+                    "Failed to remove expired isolation for pod {}: {}",
+                    pod_name, e
+                );
+            }
+        }
+    }
+
     pub async fn negotiate_with_neighbors(
         &self,
         faulty_pod: &str,
         namespace: &str,
+        service_selector: &BTreeMap<String, String>,
     ) -> Result<NeighborNegotiationResult> {
+        let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
+        let selector = service_selector
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(",");
+        let backing_pods: HashSet<String> = pods
+            .list(&ListParams::default().labels(&selector))
+            .await?
+            .items
+            .iter()
+            .filter(|pod| !pod.labels().contains_key(ISOLATED_LABEL))
+            .map(|pod| pod.name_any())
+            .collect();
+
         let all_metrics = self.prometheus.get_all_pod_metrics(namespace).await?;
 
         let mut accepting = Vec::new();
         let mut rejected = Vec::new();
 
         for pod_metrics in all_metrics {
-            if pod_metrics.pod_name == faulty_pod {
+            if pod_metrics.pod_name == faulty_pod || !backing_pods.contains(&pod_metrics.pod_name) {
                 continue;
             }
 
-            let available_capacity = 1.0 - pod_metrics.cpu_usage.max(pod_metrics.memory_usage);
+            let utilisation = match (pod_metrics.cpu_usage, pod_metrics.memory_usage) {
+                (Some(cpu), Some(memory)) => cpu.max(memory),
+                (Some(value), None) | (None, Some(value)) => value,
+                (None, None) => {
+                    rejected.push(RejectedNeighbor {
+                        pod_name: pod_metrics.pod_name,
+                        reason: "Capacity unknown: no CPU or memory samples".to_string(),
+                    });
+                    continue;
+                }
+            };
+            let available_capacity = 1.0 - utilisation;
 
             if available_capacity >= self.config.neighbor_capacity_threshold {
                 let load_fraction = (available_capacity - self.config.neighbor_capacity_threshold)
@@ -234,14 +1574,13 @@ impl ContainmentAgent {
     fn create_network_policy(
         &self,
         name: &str,
-        pod_name: &str,
+        isolation_id: &str,
         strategy: &IsolationStrategy,
+        correlation_id: Uuid,
+        owner: Option<OwnerReference>,
     ) -> NetworkPolicy {
         let mut labels = std::collections::BTreeMap::new();
-        labels.insert(
-            "statefulset.kubernetes.io/pod-name".to_string(),
-            pod_name.to_string(),
-        );
+        labels.insert(ISOLATED_LABEL.to_string(), isolation_id.to_string());
 
         let mut policy_types = vec!["Ingress".to_string()];
         if matches!(strategy, IsolationStrategy::Hard) {
@@ -257,8 +1596,11 @@ impl ContainmentAgent {
                         "app.kubernetes.io/managed-by".to_string(),
                         "recist".to_string(),
                     );
+                    l.insert(ISOLATED_LABEL.to_string(), isolation_id.to_string());
+                    l.insert(CORRELATION_ID_LABEL.to_string(), correlation_id.to_string());
                     l
                 }),
+                owner_references: owner.map(|owner| vec![owner]),
                 ..Default::default()
             },
             spec: Some(NetworkPolicySpec {
@@ -279,9 +1621,97 @@ impl ContainmentAgent {
         }
     }
 
+    async fn publish_forecasts(&self, namespace: &str, fault_cluster: &FaultCluster) {
+        let pods: HashSet<String> = match self.resolve_target_pods(namespace).await {
+            Ok(pods) => pods
+                .iter()
+                .map(|pod| pod.name_any())
+                .filter(|name| !fault_cluster.faults.iter().any(|f| &f.pod_name == name))
+                .collect(),
+            Err(e) => {
+                warn!(
+                    "Failed to resolve target pods for forecasting in {}: {}",
+                    namespace, e
+                );
+                return;
+            }
+        };
+
+        for warning in self.forecaster.forecast(namespace, &pods).await {
+            let event = AgentEvent::proactive_warning(Uuid::new_v4(), warning);
+            if let Err(e) = self.event_bus.publish(event).await {
+                error!("Failed to publish proactive warning: {}", e);
+            }
+        }
+    }
+
+    async fn contain_incident(&self, incident: Incident) {
+        let correlation_id = incident.correlation_id;
+
+        for fault in &incident.cluster.faults {
+            let healing_event = match self.create_healing_event(correlation_id, fault).await {
+                Ok(healing_event) => Some(healing_event),
+                Err(e) => {
+                    error!(
+                        "Failed to create HealingEvent for pod {}: {}",
+                        fault.pod_name, e
+                    );
+                    None
+                }
+            };
+
+            let (isolation_method, refusal_reason, downgrade_reason) = match self
+                .isolate_pod(fault, correlation_id, healing_event.as_ref())
+                .await
+            {
+                Ok(rule) => (
+                    Some(rule.rule_type.method().to_string()),
+                    None,
+                    rule.downgrade_reason,
+                ),
+                Err(RecistError::ActionRefused(reason)) => {
+                    warn!("Refused to isolate pod {}: {}", fault.pod_name, reason);
+                    (None, Some(reason), None)
+                }
+                Err(e) => {
+                    error!("Failed to isolate pod {}: {}", fault.pod_name, e);
+                    (None, None, None)
+                }
+            };
+
+            let event = AgentEvent::containment_complete_with_method(
+                correlation_id,
+                fault.pod_name.clone(),
+                fault.namespace.clone(),
+                isolation_method,
+                refusal_reason,
+                downgrade_reason,
+            );
+            if let Err(e) = self.event_bus.publish(event).await {
+                error!("Failed to publish containment event: {}", e);
+            }
+        }
+
+        if !incident.is_new {
+            debug!(
+                "Pods {:?} joined incident {}, already under diagnosis",
+                incident.cluster.pod_names(),
+                correlation_id
+            );
+            return;
+        }
+
+        let event = AgentEvent::fault_detected(correlation_id, incident.cluster);
+        if let Err(e) = self.event_bus.publish(event).await {
+            error!("Failed to publish fault event: {}", e);
+        }
+    }
+
     pub async fn run_check_loop(&self, namespaces: Vec<String>) {
         let interval = std::time::Duration::from_secs(self.config.check_interval_seconds);
 
+        self.restore_isolations(&namespaces).await;
+
         loop {
             {
                 let running = self.running.read().await;
@@ -290,22 +1720,18 @@ impl ContainmentAgent {
                 }
             }
 
+            self.expire_isolations().await;
+
             for namespace in &namespaces {
                 match self.check_metrics(namespace).await {
                     Ok(fault_cluster) => {
-                        if !fault_cluster.is_empty() {
-                            for fault in &fault_cluster.faults {
-                                if let Err(e) = self.isolate_pod(fault).await {
-                                    error!("Failed to isolate pod {}: {}", fault.pod_name, e);
-                                }
-                            }
-
-                            let correlation_id = Uuid::new_v4();
-                            let event =
-                                AgentEvent::fault_detected(correlation_id, fault_cluster.clone());
+                        if self.forecaster.enabled() {
+                            self.publish_forecasts(namespace, &fault_cluster).await;
+                        }
 
-                            if let Err(e) = self.event_bus.publish(event).await {
-                                error!("Failed to publish fault event: {}", e);
+                        if !fault_cluster.is_empty() {
+                            for incident in self.incidents.correlate(fault_cluster).await {
+                                self.contain_incident(incident).await;
                             }
                         }
                     }
@@ -348,17 +1774,279 @@ impl Agent for ContainmentAgent {
 #[async_trait]
 impl EventHandler for ContainmentAgent {
     async fn handle_event(&self, event: AgentEvent) -> Result<Option<AgentEvent>> {
-        match &event.payload {
-            crate::models::EventPayload::HealingComplete(payload) => {
+        if let crate::models::EventPayload::HealingComplete(payload) = &event.payload {
+            info!(
+                "Healing {} for correlation {}, removing isolation",
                 if payload.success {
-                    info!(
-                        "Healing complete, removing isolation for correlation {}",
-                        event.correlation_id
-                    );
+                    "succeeded"
+                } else {
+                    "failed"
+                },
+                event.correlation_id
+            );
+
+            let mut targets: HashSet<(String, String)> = payload
+                .context
+                .affected_pods
+                .iter()
+                .map(|pod_name| (pod_name.clone(), payload.context.namespace.clone()))
+                .collect();
+            targets.extend(
+                self.active_isolations
+                    .read()
+                    .await
+                    .values()
+                    .filter(|rule| rule.correlation_id == Some(event.correlation_id))
+                    .map(|rule| (rule.pod_name.clone(), rule.namespace.clone())),
+            );
+
+            for (pod_name, namespace) in targets {
+                if let Err(e) = self.remove_isolation(&pod_name, &namespace).await {
+                    warn!("Failed to remove isolation for pod {}: {}", pod_name, e);
                 }
             }
-            _ => {}
+
+            self.incidents.close(event.correlation_id).await;
         }
         Ok(None)
     }
 }
+
+fn selecting<'a>(
+    services: &'a [(String, BTreeMap<String, String>)],
+    labels: &'a BTreeMap<String, String>,
+) -> impl Iterator<Item = &'a (String, BTreeMap<String, String>)> {
+    services.iter().filter(|(_, selector)| {
+        selector_matches(
+            &LabelSelector {
+                match_labels: Some(selector.clone()),
+                ..Default::default()
+            },
+            labels,
+        )
+    })
+}
+
+fn guard_strategy(
+    strategy: IsolationStrategy,
+    availability: Availability,
+) -> Result<(IsolationStrategy, Option<String>)> {
+    match availability {
+        Availability::Allowed => Ok((strategy, None)),
+        Availability::Refused(reason) => Err(RecistError::ActionRefused(reason)),
+        Availability::Degraded(reason) => match strategy {
+            IsolationStrategy::Hard | IsolationStrategy::Quarantine => Ok((
+                IsolationStrategy::Soft,
+                Some(format!(
+                    "{:?} isolation downgraded to soft isolation: {}",
+                    strategy, reason
+                )),
+            )),
+            _ => Ok((strategy, None)),
+        },
+    }
+}
+
+fn expired_isolations<'a>(
+    rules: impl IntoIterator<Item = &'a IsolationRule>,
+    cutoff: DateTime<Utc>,
+) -> Vec<(String, String)> {
+    rules
+        .into_iter()
+        .filter(|rule| rule.created_at < cutoff)
+        .map(|rule| (rule.pod_name.clone(), rule.namespace.clone()))
+        .collect()
+}
+
+fn container_restarts(pod: &Pod) -> i32 {
+    pod.status
+        .as_ref()
+        .and_then(|s| s.container_statuses.as_ref())
+        .map(|statuses| statuses.iter().map(|s| s.restart_count).sum())
+        .unwrap_or(0)
+}
+
+fn is_ignored<K: ResourceExt>(resource: &K) -> bool {
+    resource
+        .annotations()
+        .get(IGNORE_ANNOTATION)
+        .map(|value| value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+    fn rule(pod_name: &str, age_minutes: i64) -> IsolationRule {
+        IsolationRule {
+            pod_name: pod_name.to_string(),
+            namespace: "default".to_string(),
+            network_policy_name: format!("recist-isolate-{}", pod_name),
+            isolation_id: String::new(),
+            correlation_id: None,
+            created_at: Utc::now() - ChronoDuration::minutes(age_minutes),
+            rule_type: IsolationRuleType::DenyAll,
+            redirects: Vec::new(),
+            downgrade_reason: None,
+        }
+    }
+
+    #[test]
+    fn test_guard_strategy() {
+        assert_eq!(
+            guard_strategy(IsolationStrategy::Hard, Availability::Allowed).unwrap(),
+            (IsolationStrategy::Hard, None)
+        );
+        assert!(matches!(
+            guard_strategy(
+                IsolationStrategy::Soft,
+                Availability::Refused("last ready replica".to_string())
+            ),
+            Err(RecistError::ActionRefused(reason)) if reason == "last ready replica"
+        ));
+
+        let (strategy, reason) = guard_strategy(
+            IsolationStrategy::Quarantine,
+            Availability::Degraded("1/2 replicas ready".to_string()),
+        )
+        .unwrap();
+        assert_eq!(strategy, IsolationStrategy::Soft);
+        assert_eq!(
+            reason.as_deref(),
+            Some("Quarantine isolation downgraded to soft isolation: 1/2 replicas ready")
+        );
+
+        assert_eq!(
+            guard_strategy(
+                IsolationStrategy::Soft,
+                Availability::Degraded("1/2 replicas ready".to_string())
+            )
+            .unwrap(),
+            (IsolationStrategy::Soft, None)
+        );
+    }
+
+    #[test]
+    fn test_quarantine_record_round_trip() {
+        let mut pod = Pod {
+            metadata: ObjectMeta {
+                name: Some("web-1".to_string()),
+                labels: Some(BTreeMap::from([
+                    ("app".to_string(), "web".to_string()),
+                    ("tier".to_string(), "frontend".to_string()),
+                    ("version".to_string(), "v1".to_string()),
+                ])),
+                owner_references: Some(vec![OwnerReference {
+                    api_version: "apps/v1".to_string(),
+                    kind: "ReplicaSet".to_string(),
+                    name: "web-7d9f8".to_string(),
+                    uid: "uid".to_string(),
+                    controller: Some(true),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let correlation_id = Uuid::new_v4();
+        let selector_keys = HashSet::from(["app".to_string(), "tier".to_string()]);
+
+        let record = QuarantineRecord::new(&pod, &selector_keys, correlation_id);
+        assert_eq!(record.controller.as_deref(), Some("ReplicaSet/web-7d9f8"));
+        assert_eq!(record.correlation_id, Some(correlation_id));
+
+        let patch = record.quarantine_patch("abc123").unwrap();
+        let labels = &patch["metadata"]["labels"];
+        assert_eq!(labels["app"], serde_json::Value::Null);
+        assert_eq!(labels["tier"], serde_json::Value::Null);
+        assert_eq!(labels[ISOLATED_LABEL], "abc123");
+        assert!(labels.get("version").is_none());
+
+        let annotation = patch["metadata"]["annotations"][QUARANTINE_ANNOTATION]
+            .as_str()
+            .unwrap()
+            .to_string();
+        pod.metadata.annotations = Some(BTreeMap::from([(
+            QUARANTINE_ANNOTATION.to_string(),
+            annotation,
+        )]));
+        let restored = QuarantineRecord::from_pod(&pod).unwrap().unwrap();
+        assert_eq!(restored, record);
+
+        let release = restored.release_patch();
+        assert_eq!(release["metadata"]["labels"]["app"], "web");
+        assert_eq!(release["metadata"]["labels"]["tier"], "frontend");
+        assert_eq!(
+            release["metadata"]["annotations"][QUARANTINE_ANNOTATION],
+            serde_json::Value::Null
+        );
+
+        pod.metadata.annotations = None;
+        assert!(QuarantineRecord::from_pod(&pod).is_none());
+    }
+
+    #[test]
+    fn test_expired_isolations() {
+        let rules = [rule("web-1", 120), rule("web-2", 10), rule("web-3", 61)];
+        let cutoff = Utc::now() - ChronoDuration::minutes(60);
+
+        assert_eq!(
+            expired_isolations(&rules, cutoff),
+            vec![
+                ("web-1".to_string(), "default".to_string()),
+                ("web-3".to_string(), "default".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_assess_hysteresis() {
+        let fault_state = FaultStateTracker::new();
+        let criterion = |value: f64| Criterion {
+            key: "threshold/cpu".to_string(),
+            name: "cpu",
+            query: String::new(),
+            value: Some(value),
+            operator: ComparisonOperator::GreaterThan,
+            threshold: 80.0,
+            clear_threshold: 70.0,
+            for_seconds: 0,
+            min_checks: 2,
+        };
+
+        let mut breaches = Vec::new();
+        for value in [85.0, 65.0, 85.0, 85.0, 75.0, 65.0, 85.0] {
+            let criterion = criterion(value);
+            let crossing = fault_state
+                .observe(
+                    "default",
+                    "web-1",
+                    &criterion.key,
+                    criterion.operator.compare(value, criterion.threshold),
+                    criterion.operator.compare(value, criterion.clear_threshold),
+                )
+                .await;
+            let breached = criterion.breach(crossing).unwrap();
+            if breached && !crossing.was_active() {
+                fault_state
+                    .set_active("default", "web-1", &criterion.key, true)
+                    .await;
+            }
+            breaches.push(breached);
+        }
+
+        assert_eq!(
+            breaches,
+            vec![false, false, false, true, true, false, false]
+        );
+
+        let sustained = Criterion {
+            for_seconds: 60,
+            ..criterion(85.0)
+        };
+        assert_eq!(sustained.breach(Crossing::Triggering(2)), None);
+        assert_eq!(sustained.breach(Crossing::Triggering(1)), Some(false));
+    }
+}
@@ -9,21 +9,22 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use super::traits::{Agent, EventHandler};
-use crate::clients::llm::{DiagnosisRequest, LlmClient, MetricSnapshot};
+use crate::clients::llm::{DiagnosisRequest, LlmClientHandle, MetricSnapshot};
 use crate::clients::{LokiClient, PrometheusClient};
 use crate::crd::DiagnosisConfig;
 use crate::error::{RecistError, Result};
 use crate::eventbus::EventBus;
 use crate::models::{
     AgentEvent, AgentEventType, AgentType, CausalNode, CausalNodeType, CausalRelation, CausalTree,
-    DiagnosisHypothesis, EventPayload, Evidence, EvidenceSource, FaultCluster, StructuredLog,
+    DiagnosisHypothesis, EventPayload, Evidence, EvidenceSource, Fault, FaultCluster, FaultContext,
+    StructuredLog,
 };
 
 pub struct DiagnosisAgent {
     kube_client: KubeClient,
     prometheus: Arc<PrometheusClient>,
     loki: Arc<LokiClient>,
-    llm: Arc<dyn LlmClient>,
+    llm: LlmClientHandle,
     event_bus: EventBus,
     config: DiagnosisConfig,
 }
@@ -32,7 +33,7 @@ impl DiagnosisAgent {
     pub async fn new(
         prometheus: Arc<PrometheusClient>,
         loki: Arc<LokiClient>,
-        llm: Arc<dyn LlmClient>,
+        llm: LlmClientHandle,
         event_bus: EventBus,
         config: DiagnosisConfig,
     ) -> Result<Self> {
@@ -56,8 +57,10 @@ impl DiagnosisAgent {
             .ok_or_else(|| RecistError::DiagnosisError("No faults in cluster".to_string()))?;
 
         info!(
-            "Starting diagnosis for pod {}/{}",
-            fault.namespace, fault.pod_name
+            "Starting diagnosis for pod {}/{} ({} correlated faults)",
+            fault.namespace,
+            fault.pod_name,
+            fault_cluster.faults.len() - 1
         );
 
         let logs = self.collect_logs(&fault.namespace, &fault.pod_name).await?;
@@ -79,14 +82,26 @@ impl DiagnosisAgent {
                     value: *value,
                     threshold: None,
                 })
+                .chain(fault.signals.iter().map(|signal| MetricSnapshot {
+                    name: signal.name.clone(),
+                    value: signal.value,
+                    threshold: Some(signal.threshold),
+                }))
                 .collect(),
             kubernetes_events: k8s_events,
             pod_name: fault.pod_name.clone(),
             namespace: fault.namespace.clone(),
             error_type: fault.primary_reason().to_string(),
+            related_faults: fault_cluster
+                .faults
+                .iter()
+                .filter(|f| f.pod_name != fault.pod_name)
+                .map(describe_fault)
+                .collect(),
         };
 
-        let llm_response = self.llm.diagnose(&llm_request).await?;
+        let llm = self.llm.get().await?;
+        let llm_response = llm.diagnose(&llm_request).await?;
 
         let mut hypothesis = DiagnosisHypothesis::new(
             llm_response.explanation.clone(),
@@ -352,10 +367,24 @@ impl EventHandler for DiagnosisAgent {
                     event.correlation_id
                 );
 
+                let context = match FaultContext::from_cluster(&payload.fault_cluster) {
+                    Some(context) => context,
+                    None => {
+                        warn!(
+                            "Fault event for correlation {} has no faults, skipping diagnosis",
+                            event.correlation_id
+                        );
+                        return Ok(None);
+                    }
+                };
+
                 match self.diagnose(&payload.fault_cluster).await {
                     Ok(hypothesis) => {
-                        let response =
-                            AgentEvent::diagnosis_complete(event.correlation_id, hypothesis);
+                        let response = AgentEvent::diagnosis_complete(
+                            event.correlation_id,
+                            context,
+                            hypothesis,
+                        );
                         return Ok(Some(response));
                     }
                     Err(e) => {
@@ -369,3 +398,29 @@ impl EventHandler for DiagnosisAgent {
         Ok(None)
     }
 }
+
+fn describe_fault(fault: &Fault) -> String {
+    let mut placement = Vec::new();
+    if let Some(workload) = &fault.workload {
+        placement.push(workload.to_string());
+    }
+    if let Some(node_name) = &fault.node_name {
+        placement.push(format!("node {}", node_name));
+    }
+    if !fault.services.is_empty() {
+        placement.push(format!("services {}", fault.services.join(",")));
+    }
+
+    format!(
+        "{} [{}] {:?}: {}",
+        fault.pod_name,
+        placement.join("; "),
+        fault.severity,
+        fault
+            .reasons
+            .iter()
+            .map(|r| r.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
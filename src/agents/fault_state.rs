@@ -0,0 +1,163 @@
+use std::collections::{HashMap, HashSet};
+use tokio::sync::RwLock;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Crossing {
+    Clear,
+    Triggering(u32),
+    Holding,
+    Cleared,
+}
+
+impl Crossing {
+    pub fn was_active(&self) -> bool {
+        matches!(self, Crossing::Holding | Crossing::Cleared)
+    }
+}
+
+#[derive(Default)]
+pub struct FaultStateTracker {
+    active: RwLock<HashMap<String, HashSet<String>>>,
+    streaks: RwLock<HashMap<String, HashMap<String, u32>>>,
+}
+
+impl FaultStateTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn is_active(&self, namespace: &str, pod_name: &str, key: &str) -> bool {
+        self.active
+            .read()
+            .await
+            .get(&pod_key(namespace, pod_name))
+            .map(|keys| keys.contains(key))
+            .unwrap_or(false)
+    }
+
+    pub async fn set_active(&self, namespace: &str, pod_name: &str, key: &str, active: bool) {
+        let mut states = self.active.write().await;
+        let pod = pod_key(namespace, pod_name);
+        clear_streak(&mut *self.streaks.write().await, &pod, key);
+
+        if active {
+            states.entry(pod).or_default().insert(key.to_string());
+        } else if let Some(keys) = states.get_mut(&pod) {
+            keys.remove(key);
+            if keys.is_empty() {
+                states.remove(&pod);
+            }
+        }
+    }
+
+    pub async fn observe(
+        &self,
+        namespace: &str,
+        pod_name: &str,
+        key: &str,
+        triggered: bool,
+        holding: bool,
+    ) -> Crossing {
+        if !self.is_active(namespace, pod_name, key).await {
+            let mut streaks = self.streaks.write().await;
+            let pod = pod_key(namespace, pod_name);
+            if !triggered {
+                clear_streak(&mut streaks, &pod, key);
+                return Crossing::Clear;
+            }
+
+            let streak = streaks
+                .entry(pod)
+                .or_default()
+                .entry(key.to_string())
+                .or_default();
+            *streak += 1;
+            return Crossing::Triggering(*streak);
+        }
+
+        if holding {
+            Crossing::Holding
+        } else {
+            self.set_active(namespace, pod_name, key, false).await;
+            Crossing::Cleared
+        }
+    }
+
+    pub async fn retain_pods(&self, namespace: &str, pod_names: &HashSet<String>) {
+        let prefix = format!("{}/", namespace);
+        let keep = |pod: &String| {
+            pod.strip_prefix(&prefix)
+                .map(|name| pod_names.contains(name))
+                .unwrap_or(true)
+        };
+        self.active.write().await.retain(|pod, _| keep(pod));
+        self.streaks.write().await.retain(|pod, _| keep(pod));
+    }
+}
+
+fn clear_streak(streaks: &mut HashMap<String, HashMap<String, u32>>, pod: &str, key: &str) {
+    if let Some(keys) = streaks.get_mut(pod) {
+        keys.remove(key);
+        if keys.is_empty() {
+            streaks.remove(pod);
+        }
+    }
+}
+
+fn pod_key(namespace: &str, pod_name: &str) -> String {
+    format!("{}/{}", namespace, pod_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crd::ComparisonOperator;
+
+    async fn observe(tracker: &FaultStateTracker, pod_name: &str, value: f64) -> Crossing {
+        let operator = ComparisonOperator::GreaterThan;
+        tracker
+            .observe(
+                "default",
+                pod_name,
+                "threshold/cpu",
+                operator.compare(value, 0.8),
+                operator.compare(value, 0.7),
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_trigger_and_clear_hysteresis() {
+        let tracker = FaultStateTracker::new();
+
+        assert_eq!(observe(&tracker, "web-1", 0.75).await, Crossing::Clear);
+        assert_eq!(
+            observe(&tracker, "web-1", 0.85).await,
+            Crossing::Triggering(1)
+        );
+        assert_eq!(
+            observe(&tracker, "web-1", 0.85).await,
+            Crossing::Triggering(2)
+        );
+        assert_eq!(observe(&tracker, "web-1", 0.75).await, Crossing::Clear);
+        assert_eq!(
+            observe(&tracker, "web-1", 0.85).await,
+            Crossing::Triggering(1)
+        );
+
+        tracker
+            .set_active("default", "web-1", "threshold/cpu", true)
+            .await;
+        assert_eq!(observe(&tracker, "web-1", 0.75).await, Crossing::Holding);
+        assert_eq!(observe(&tracker, "web-1", 0.65).await, Crossing::Cleared);
+        assert_eq!(observe(&tracker, "web-1", 0.75).await, Crossing::Clear);
+
+        tracker
+            .set_active("default", "web-2", "threshold/cpu", true)
+            .await;
+        tracker
+            .retain_pods("default", &HashSet::from(["web-1".to_string()]))
+            .await;
+        assert_eq!(observe(&tracker, "web-2", 0.75).await, Crossing::Clear);
+    }
+}
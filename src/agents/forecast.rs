@@ -0,0 +1,311 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::clients::PrometheusClient;
+use crate::crd::{ForecastConfig, ForecastMethod, Thresholds, TriggerReason};
+use crate::models::{ProactiveWarningPayload, TrendAnalysis, TrendDirection};
+
+pub const PREEMPTIVE_SCALE_ACTION: &str = "HorizontalScale";
+
+const STABLE_FRACTION: f64 = 0.01;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Trend {
+    pub level: f64,
+    pub slope_per_minute: f64,
+    pub fit: f64,
+}
+
+impl Trend {
+    pub fn linear(samples: &[(DateTime<Utc>, f64)]) -> Option<Self> {
+        let (origin, _) = samples.first()?;
+        let points: Vec<(f64, f64)> = samples
+            .iter()
+            .map(|(t, v)| ((*t - *origin).num_seconds() as f64 / 60.0, *v))
+            .collect();
+
+        let n = points.len() as f64;
+        if n < 2.0 {
+            return None;
+        }
+
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let sxx = points
+            .iter()
+            .map(|(x, _)| (x - mean_x).powi(2))
+            .sum::<f64>();
+        let sxy = points
+            .iter()
+            .map(|(x, y)| (x - mean_x) * (y - mean_y))
+            .sum::<f64>();
+        let syy = points
+            .iter()
+            .map(|(_, y)| (y - mean_y).powi(2))
+            .sum::<f64>();
+
+        if sxx == 0.0 {
+            return None;
+        }
+
+        let slope = sxy / sxx;
+        let intercept = mean_y - slope * mean_x;
+        let last_x = points.last().map(|(x, _)| *x).unwrap_or(0.0);
+        let fit = if syy == 0.0 {
+            1.0
+        } else {
+            (sxy * sxy) / (sxx * syy)
+        };
+
+        Some(Self {
+            level: intercept + slope * last_x,
+            slope_per_minute: slope,
+            fit,
+        })
+    }
+
+    pub fn holt(
+        samples: &[(DateTime<Utc>, f64)],
+        alpha: f64,
+        beta: f64,
+        step_minutes: f64,
+    ) -> Option<Self> {
+        if samples.len() < 2 || step_minutes <= 0.0 {
+            return None;
+        }
+
+        let alpha = alpha.clamp(0.0, 1.0);
+        let beta = beta.clamp(0.0, 1.0);
+
+        let mut level = samples[0].1;
+        let mut trend = samples[1].1 - samples[0].1;
+        let mut abs_error = 0.0;
+        let mut abs_total = 0.0;
+
+        for (_, value) in &samples[1..] {
+            let forecast = level + trend;
+            abs_error += (value - forecast).abs();
+            abs_total += value.abs();
+
+            let previous_level = level;
+            level = alpha * value + (1.0 - alpha) * (level + trend);
+            trend = beta * (level - previous_level) + (1.0 - beta) * trend;
+        }
+
+        let fit = if abs_total == 0.0 {
+            1.0
+        } else {
+            (1.0 - abs_error / abs_total).clamp(0.0, 1.0)
+        };
+
+        Some(Self {
+            level,
+            slope_per_minute: trend / step_minutes,
+            fit,
+        })
+    }
+
+    pub fn minutes_until(&self, threshold: f64) -> Option<f64> {
+        if self.level >= threshold || self.slope_per_minute <= 0.0 {
+            return None;
+        }
+        Some((threshold - self.level) / self.slope_per_minute)
+    }
+}
+
+pub struct TrendForecaster {
+    prometheus: Arc<PrometheusClient>,
+    thresholds: Thresholds,
+    config: ForecastConfig,
+    scaling_allowed: bool,
+    warned: RwLock<HashMap<String, DateTime<Utc>>>,
+}
+
+impl TrendForecaster {
+    pub fn new(
+        prometheus: Arc<PrometheusClient>,
+        thresholds: Thresholds,
+        config: ForecastConfig,
+        scaling_allowed: bool,
+    ) -> Self {
+        Self {
+            prometheus,
+            thresholds,
+            config,
+            scaling_allowed,
+            warned: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    pub async fn forecast(
+        &self,
+        namespace: &str,
+        pods: &HashSet<String>,
+    ) -> Vec<ProactiveWarningPayload> {
+        let step = self.config.step_seconds.max(1);
+        let end = Utc::now();
+        let start = end - ChronoDuration::minutes(self.config.lookback_minutes as i64);
+
+        let history = match self
+            .prometheus
+            .get_pod_metric_history(namespace, start, end, step)
+            .await
+        {
+            Ok(history) => history,
+            Err(e) => {
+                warn!(
+                    "Failed to load metric history for forecasting in {}: {}",
+                    namespace, e
+                );
+                return Vec::new();
+            }
+        };
+
+        let mut warnings = Vec::new();
+        let mut warned = self.warned.write().await;
+        let cooldown = ChronoDuration::minutes(self.config.horizon_minutes as i64);
+        warned.retain(|_, at| end - *at < cooldown);
+
+        for (pod_name, metrics) in history.iter().filter(|(pod, _)| pods.contains(*pod)) {
+            for (metric, reason, threshold) in [
+                ("cpu", TriggerReason::HighCpu, self.thresholds.cpu),
+                ("memory", TriggerReason::HighMemory, self.thresholds.memory),
+                (
+                    "errorRate",
+                    TriggerReason::HighErrorRate,
+                    self.thresholds.error_rate,
+                ),
+                (
+                    "latency",
+                    TriggerReason::HighLatency,
+                    self.thresholds.latency_ms as f64,
+                ),
+            ] {
+                let samples = match metrics.get(metric) {
+                    Some(samples) if samples.len() >= self.config.min_samples => samples,
+                    _ => continue,
+                };
+
+                let trend = match self.config.method {
+                    ForecastMethod::Linear => Trend::linear(samples),
+                    ForecastMethod::Holt => Trend::holt(
+                        samples,
+                        self.config.smoothing_alpha,
+                        self.config.smoothing_beta,
+                        step as f64 / 60.0,
+                    ),
+                };
+                let trend = match trend {
+                    Some(trend) => trend,
+                    None => continue,
+                };
+
+                let analysis = self.analyze(namespace, metric, threshold, &trend);
+                let breach_minutes = match analysis.predicted_threshold_breach_minutes {
+                    Some(minutes) => minutes,
+                    None => continue,
+                };
+
+                let key = format!("{}/{}/{}", namespace, pod_name, metric);
+                if warned.contains_key(&key) {
+                    debug!("Already warned about {} within the horizon", key);
+                    continue;
+                }
+                warned.insert(key, end);
+
+                info!(
+                    "Forecast: {} on pod {}/{} predicted to breach {} in {} minutes",
+                    metric, namespace, pod_name, threshold, breach_minutes
+                );
+
+                warnings.push(ProactiveWarningPayload {
+                    namespace: namespace.to_string(),
+                    pod_name: Some(pod_name.clone()),
+                    warning_type: format!("Predicted{}", reason),
+                    message: format!(
+                        "{} is {:.3} and rising {:.4}/min; predicted to exceed {} in {} minutes",
+                        metric,
+                        analysis.current_value,
+                        analysis.change_rate_per_minute,
+                        threshold,
+                        breach_minutes
+                    ),
+                    suggested_action: self
+                        .scaling_allowed
+                        .then(|| PREEMPTIVE_SCALE_ACTION.to_string()),
+                    confidence: trend.fit,
+                    trend: Some(analysis),
+                    reason: Some(reason),
+                });
+            }
+        }
+
+        warnings
+    }
+
+    fn analyze(
+        &self,
+        namespace: &str,
+        metric: &str,
+        threshold: f64,
+        trend: &Trend,
+    ) -> TrendAnalysis {
+        let horizon = self.config.horizon_minutes as f64;
+        let projected_change = trend.slope_per_minute * horizon;
+
+        let trend_direction = if projected_change.abs() <= threshold.abs() * STABLE_FRACTION {
+            TrendDirection::Stable
+        } else if projected_change > 0.0 {
+            TrendDirection::Increasing
+        } else {
+            TrendDirection::Decreasing
+        };
+
+        let predicted_threshold_breach_minutes = match trend_direction {
+            TrendDirection::Increasing => trend
+                .minutes_until(threshold)
+                .filter(|minutes| *minutes <= horizon)
+                .map(|minutes| minutes.ceil() as u64),
+            _ => None,
+        };
+
+        TrendAnalysis {
+            namespace: namespace.to_string(),
+            metric_name: metric.to_string(),
+            current_value: trend.level,
+            trend_direction,
+            change_rate_per_minute: trend.slope_per_minute,
+            predicted_threshold_breach_minutes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trend_predicts_breach() {
+        let start = Utc::now() - ChronoDuration::minutes(10);
+        let samples: Vec<(DateTime<Utc>, f64)> = (0..10)
+            .map(|i| (start + ChronoDuration::minutes(i), 0.5 + 0.02 * i as f64))
+            .collect();
+
+        let linear = Trend::linear(&samples).unwrap();
+        assert!((linear.slope_per_minute - 0.02).abs() < 1e-9);
+        assert!((linear.level - 0.68).abs() < 1e-9);
+        assert!((linear.minutes_until(0.9).unwrap() - 11.0).abs() < 1e-6);
+        assert_eq!(linear.minutes_until(0.5), None);
+
+        let holt = Trend::holt(&samples, 0.5, 0.3, 1.0).unwrap();
+        assert!((holt.slope_per_minute - 0.02).abs() < 1e-9);
+        assert!(holt.minutes_until(0.9).is_some());
+    }
+}
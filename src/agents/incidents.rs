@@ -0,0 +1,133 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use tokio::sync::RwLock;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::models::FaultCluster;
+
+#[derive(Clone, Debug)]
+pub struct Incident {
+    pub correlation_id: Uuid,
+    pub cluster: FaultCluster,
+    pub is_new: bool,
+}
+
+struct OpenIncident {
+    correlation_id: Uuid,
+    cluster: FaultCluster,
+    updated_at: DateTime<Utc>,
+}
+
+pub struct IncidentCorrelator {
+    window: ChronoDuration,
+    open: RwLock<Vec<OpenIncident>>,
+}
+
+impl IncidentCorrelator {
+    pub fn new(window_seconds: u64) -> Self {
+        Self {
+            window: ChronoDuration::seconds(window_seconds as i64),
+            open: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub async fn correlate(&self, fault_cluster: FaultCluster) -> Vec<Incident> {
+        let now = Utc::now();
+        let mut open = self.open.write().await;
+        open.retain(|incident| now - incident.updated_at <= self.window);
+
+        let mut incidents = Vec::new();
+        for cluster in fault_cluster.into_incidents(self.window) {
+            let existing = open.iter_mut().find(|incident| {
+                cluster
+                    .faults
+                    .iter()
+                    .any(|fault| incident.cluster.correlates_with(fault, self.window))
+            });
+
+            match existing {
+                Some(incident) => {
+                    info!(
+                        "Faults in pods {:?} joined incident {}",
+                        cluster.pod_names(),
+                        incident.correlation_id
+                    );
+                    for fault in &cluster.faults {
+                        incident.cluster.add_fault(fault.clone());
+                    }
+                    incident.updated_at = now;
+                    incidents.push(Incident {
+                        correlation_id: incident.correlation_id,
+                        cluster,
+                        is_new: false,
+                    });
+                }
+                None => {
+                    let correlation_id = Uuid::new_v4();
+                    info!(
+                        "Opened incident {} for pods {:?}",
+                        correlation_id,
+                        cluster.pod_names()
+                    );
+                    open.push(OpenIncident {
+                        correlation_id,
+                        cluster: cluster.clone(),
+                        updated_at: now,
+                    });
+                    incidents.push(Incident {
+                        correlation_id,
+                        cluster,
+                        is_new: true,
+                    });
+                }
+            }
+        }
+
+        incidents
+    }
+
+    pub async fn close(&self, correlation_id: Uuid) {
+        self.open
+            .write()
+            .await
+            .retain(|incident| incident.correlation_id != correlation_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crd::TriggerReason;
+    use crate::models::test_support::fault;
+
+    fn cluster(pod_names: &[&str]) -> FaultCluster {
+        let mut cluster = FaultCluster::new("default".to_string());
+        for pod_name in pod_names {
+            cluster.add_fault(fault(
+                pod_name,
+                "web",
+                "node-a",
+                TriggerReason::HighErrorRate,
+            ));
+        }
+        cluster
+    }
+
+    #[tokio::test]
+    async fn test_late_replica_joins_open_incident() {
+        let correlator = IncidentCorrelator::new(120);
+
+        let first = correlator.correlate(cluster(&["web-1", "web-2"])).await;
+        assert_eq!(first.len(), 1);
+        assert!(first[0].is_new);
+
+        let second = correlator.correlate(cluster(&["web-3"])).await;
+        assert_eq!(second.len(), 1);
+        assert!(!second[0].is_new);
+        assert_eq!(second[0].correlation_id, first[0].correlation_id);
+
+        correlator.close(first[0].correlation_id).await;
+        let third = correlator.correlate(cluster(&["web-4"])).await;
+        assert!(third[0].is_new);
+    }
+}
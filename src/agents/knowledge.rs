@@ -5,7 +5,7 @@ use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 use super::traits::{Agent, EventHandler};
-use crate::clients::llm::LlmClient;
+use crate::clients::llm::LlmClientHandle;
 use crate::clients::{LocalKnowledgeCache, QdrantClient, RedisClient};
 use crate::crd::KnowledgeConfig;
 use crate::error::{RecistError, Result};
@@ -19,7 +19,7 @@ use crate::models::{
 pub struct KnowledgeAgent {
     qdrant: Arc<QdrantClient>,
     local_cache: Arc<LocalKnowledgeCache>,
-    llm: Arc<dyn LlmClient>,
+    llm: LlmClientHandle,
     event_bus: EventBus,
     config: KnowledgeConfig,
 }
@@ -28,7 +28,7 @@ impl KnowledgeAgent {
     pub async fn new(
         qdrant: Arc<QdrantClient>,
         redis: Arc<RedisClient>,
-        llm: Arc<dyn LlmClient>,
+        llm: LlmClientHandle,
         event_bus: EventBus,
         config: KnowledgeConfig,
         namespace: String,
@@ -50,6 +50,7 @@ impl KnowledgeAgent {
 
     pub async fn record_healing_event(
         &self,
+        correlation_id: Uuid,
         namespace: &str,
         pod_name: &str,
         error_type: &str,
@@ -74,7 +75,7 @@ impl KnowledgeAgent {
         entry.set_ttl_days(self.config.knowledge_ttl_days);
 
         let summary = entry.summary_text();
-        match self.llm.generate_embedding(&summary).await {
+        match self.generate_embedding(&summary).await {
             Ok(embedding) => {
                 entry.set_embedding(embedding);
             }
@@ -96,7 +97,7 @@ impl KnowledgeAgent {
 
         info!("Recorded knowledge entry: {}", entry.id);
 
-        let event = AgentEvent::knowledge_updated(Uuid::new_v4(), entry.clone());
+        let event = AgentEvent::knowledge_updated(correlation_id, entry.clone());
         self.event_bus.publish(event).await?;
 
         Ok(entry)
@@ -116,7 +117,7 @@ impl KnowledgeAgent {
             }]);
         }
 
-        let embedding = self.llm.generate_embedding(error_type).await?;
+        let embedding = self.generate_embedding(error_type).await?;
 
         let results = self
             .qdrant
@@ -171,6 +172,11 @@ impl KnowledgeAgent {
         Ok(best.map(|s| s.entry.solution.strategy_type))
     }
 
+    async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>> {
+        let llm = self.llm.get().await?;
+        llm.generate_embedding(text).await
+    }
+
     async fn determine_topic(&self, entry: &KnowledgeEntry) -> Result<String> {
         let root_cause_lower = entry.diagnosis.root_cause.to_lowercase();
 
@@ -306,31 +312,29 @@ impl EventHandler for KnowledgeAgent {
     async fn handle_event(&self, event: AgentEvent) -> Result<Option<AgentEvent>> {
         match &event.payload {
             EventPayload::HealingComplete(payload) => {
+                let context = &payload.context;
+
                 info!(
-                    "Received healing complete event for correlation {}",
-                    event.correlation_id
+                    "Received healing complete event for {}/{} (correlation {})",
+                    context.namespace, context.pod_name, event.correlation_id
                 );
 
-                let diagnosis_summary = DiagnosisSummary {
-                    hypothesis: String::new(),
-                    confidence: payload.strategy.confidence,
-                    root_cause: String::new(),
-                    key_evidence: vec![],
-                };
+                let diagnosis_summary = DiagnosisSummary::from(&payload.hypothesis);
 
                 let solution_summary = SolutionSummary::from(&payload.strategy);
 
                 let outcome_summary = OutcomeSummary {
                     success: payload.success,
                     message: payload.message.clone(),
-                    total_duration_ms: 0,
+                    total_duration_ms: context.elapsed_ms(),
                 };
 
                 if let Err(e) = self
                     .record_healing_event(
-                        "default",
-                        "unknown",
-                        &payload.strategy.strategy_type.to_string(),
+                        event.correlation_id,
+                        &context.namespace,
+                        &context.pod_name,
+                        &context.primary_reason().to_string(),
                         diagnosis_summary,
                         solution_summary,
                         outcome_summary,
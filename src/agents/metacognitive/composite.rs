@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use crate::crd::ActionType;
+use crate::models::{MicroAgentResult, PlannedAction, RiskLevel, StrategyType};
+
+pub fn select_compatible(
+    results: &[MicroAgentResult],
+    max_steps: usize,
+    max_risk_score: f64,
+) -> Vec<&MicroAgentResult> {
+    let mut ranked: Vec<&MicroAgentResult> = results.iter().collect();
+    ranked.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+
+    let mut selected: Vec<&MicroAgentResult> = Vec::new();
+    for result in ranked {
+        if selected.len() >= max_steps {
+            break;
+        }
+        // Isolation is owned by the containment agent, composites never select it.
+        if matches!(
+            result.strategy_type,
+            StrategyType::Composite | StrategyType::NetworkIsolation
+        ) {
+            continue;
+        }
+        if !selected
+            .iter()
+            .all(|s| compatible(&s.strategy_type, &result.strategy_type))
+        {
+            continue;
+        }
+        if !selected.is_empty()
+            && risk_score(selected.iter().copied().chain([result])) > max_risk_score
+        {
+            continue;
+        }
+        selected.push(result);
+    }
+
+    selected
+}
+
+pub fn sequence(mut actions: Vec<PlannedAction>) -> Vec<PlannedAction> {
+    let isolation = actions
+        .iter()
+        .find(|action| action.action_type == ActionType::NetworkIsolation)
+        .map(|action| action.target.clone());
+    if let Some(target) = isolation {
+        actions.push(PlannedAction {
+            action_type: ActionType::NetworkRestore,
+            target,
+            parameters: HashMap::new(),
+            order: 0,
+            depends_on: vec![],
+        });
+    }
+
+    actions.sort_by_key(|action| phase(&action.action_type));
+
+    let mut current_phase = None;
+    let mut previous_orders = Vec::new();
+    let mut current_orders = Vec::new();
+    for (index, action) in actions.iter_mut().enumerate() {
+        let action_phase = phase(&action.action_type);
+        if current_phase != Some(action_phase) {
+            previous_orders = std::mem::take(&mut current_orders);
+            current_phase = Some(action_phase);
+        }
+
+        action.order = index as u32 + 1;
+        action.depends_on = previous_orders.clone();
+        current_orders.push(action.order);
+    }
+
+    actions
+}
+
+fn risk_score<'a>(steps: impl IntoIterator<Item = &'a MicroAgentResult>) -> f64 {
+    RiskLevel::combined_score(
+        steps
+            .into_iter()
+            .map(|step| step.strategy_type.default_risk_level()),
+    )
+}
+
+fn compatible(a: &StrategyType, b: &StrategyType) -> bool {
+    if a == b {
+        return false;
+    }
+
+    // Resizing replaces the pod and config updates plan their own restart, a separate one is redundant.
+    !matches!(
+        (a, b),
+        (
+            StrategyType::PodRestart,
+            StrategyType::VerticalScale | StrategyType::ConfigUpdate
+        ) | (
+            StrategyType::VerticalScale | StrategyType::ConfigUpdate,
+            StrategyType::PodRestart
+        )
+    )
+}
+
+fn phase(action_type: &ActionType) -> u32 {
+    match action_type {
+        ActionType::NetworkIsolation => 0,
+        ActionType::HorizontalScale => 1,
+        ActionType::ConfigUpdate | ActionType::VerticalScale => 2,
+        ActionType::DependencyRestart => 3,
+        ActionType::PodRestart => 4,
+        ActionType::NetworkRestore => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ActionTarget, ResourceType, SolutionStrategy};
+    use chrono::Utc;
+
+    fn result(strategy_type: StrategyType, confidence: f64) -> MicroAgentResult {
+        MicroAgentResult {
+            agent_id: strategy_type.to_string(),
+            hypothesis: "memory leak under load".to_string(),
+            strategy_type,
+            confidence,
+            reasoning_depth: 1,
+            evidence: vec![],
+            completed_at: Utc::now(),
+        }
+    }
+
+    fn action(action_type: ActionType) -> PlannedAction {
+        PlannedAction {
+            action_type,
+            target: ActionTarget {
+                resource_type: ResourceType::Pod,
+                name: "web-1".to_string(),
+                namespace: "default".to_string(),
+            },
+            parameters: HashMap::new(),
+            order: 1,
+            depends_on: vec![],
+        }
+    }
+
+    #[test]
+    fn test_composite_plan() {
+        let results = vec![
+            result(StrategyType::NetworkIsolation, 0.95),
+            result(StrategyType::PodRestart, 0.9),
+            result(StrategyType::VerticalScale, 0.85),
+            result(StrategyType::HorizontalScale, 0.8),
+            result(StrategyType::DependencyRestart, 0.75),
+        ];
+
+        let steps = |max_risk_score| -> Vec<StrategyType> {
+            select_compatible(&results, 3, max_risk_score)
+                .into_iter()
+                .map(|r| r.strategy_type.clone())
+                .collect()
+        };
+
+        let limited = steps(0.3);
+        assert_eq!(
+            limited,
+            vec![StrategyType::PodRestart, StrategyType::HorizontalScale]
+        );
+        assert!(SolutionStrategy::composite(&limited, 0.8).risk_score <= 0.3);
+
+        let full = steps(1.0);
+        assert_eq!(
+            full,
+            vec![
+                StrategyType::PodRestart,
+                StrategyType::HorizontalScale,
+                StrategyType::DependencyRestart
+            ]
+        );
+
+        let plan: Vec<(ActionType, u32, Vec<u32>)> = sequence(
+            full.iter()
+                .map(|step| action(step.to_action_type()))
+                .collect(),
+        )
+        .into_iter()
+        .map(|a| (a.action_type, a.order, a.depends_on))
+        .collect();
+        assert_eq!(
+            plan,
+            vec![
+                (ActionType::HorizontalScale, 1, vec![]),
+                (ActionType::DependencyRestart, 2, vec![1]),
+                (ActionType::PodRestart, 3, vec![2]),
+            ]
+        );
+
+        let strategy = SolutionStrategy::composite(&full, 0.75);
+        assert_eq!(strategy.risk_level, RiskLevel::High);
+    }
+
+    #[test]
+    fn test_sequence_restores_isolated_network() {
+        let plan: Vec<(ActionType, u32, Vec<u32>)> = sequence(vec![
+            action(ActionType::PodRestart),
+            action(ActionType::HorizontalScale),
+            action(ActionType::NetworkIsolation),
+        ])
+        .into_iter()
+        .map(|a| (a.action_type, a.order, a.depends_on))
+        .collect();
+
+        assert_eq!(
+            plan,
+            vec![
+                (ActionType::NetworkIsolation, 1, vec![]),
+                (ActionType::HorizontalScale, 2, vec![1]),
+                (ActionType::PodRestart, 3, vec![2]),
+                (ActionType::NetworkRestore, 4, vec![3]),
+            ]
+        );
+    }
+}
@@ -1,51 +1,80 @@
+mod composite;
 mod micro_agent;
+mod plan;
+mod rollback;
 
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
 use futures::future::join_all;
-use k8s_openapi::api::apps::v1::Deployment;
-use k8s_openapi::api::core::v1::Pod;
-use kube::api::{DeleteParams, Patch, PatchParams};
+use k8s_openapi::api::core::v1::{ConfigMap, Pod};
+use k8s_openapi::api::networking::v1::NetworkPolicy;
+use kube::api::{DeleteParams, Patch, PatchParams, PostParams};
 use kube::{Api, Client as KubeClient};
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{BTreeMap, HashMap};
+use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+use super::availability::{Availability, AvailabilityGuard};
+use super::forecast::PREEMPTIVE_SCALE_ACTION;
 use super::traits::{Agent, EventHandler};
-use crate::clients::llm::LlmClient;
-use crate::crd::{ActionType, MetaCognitiveConfig};
+use super::workload::WorkloadResolver;
+use crate::clients::llm::LlmClientHandle;
+use crate::crd::{
+    ActionResult as AppliedActionResult, ActionType, AppliedAction, ForecastConfig, HealingEvent,
+    HealingEventSpec, HealingEventStatus, HealingOutcome, HealingPhase, MetaCognitiveConfig,
+    TriggerReason, POLICY_NAMESPACE_LABEL, POLICY_NAME_LABEL,
+};
 use crate::error::{RecistError, Result};
 use crate::eventbus::EventBus;
 use crate::models::{
     ActionResult, ActionTarget, AgentEvent, AgentEventType, AgentType, DiagnosisHypothesis,
-    EventPayload, MicroAgentResult, PlannedAction, ResourceType, RiskLevel, RollbackAction,
-    RollbackActionType, RollbackPlan, SolutionStrategy, StrategyType,
+    EventPayload, HealingCompletePayload, MicroAgentResult, OriginalState, PlannedAction,
+    ProactiveWarningPayload, ResourceType, RiskLevel, RollbackAction, RollbackActionType,
+    RollbackPlan, SolutionStrategy, StrategyType, WorkloadRef,
 };
+use composite::{select_compatible, sequence};
 use micro_agent::MicroAgent;
+use plan::execution_waves;
+use rollback::{config_snapshot, restore_config_patch, restore_resources_patch};
+
+const ADDITIONAL_REPLICAS_PARAM: &str = "additional_replicas";
+const MAX_REPLICAS_PARAM: &str = "max_replicas";
 
 pub struct MetaCognitiveAgent {
     kube_client: KubeClient,
-    llm: Arc<dyn LlmClient>,
+    availability: AvailabilityGuard,
+    workloads: WorkloadResolver,
+    llm: LlmClientHandle,
     event_bus: EventBus,
     config: MetaCognitiveConfig,
+    forecasting: ForecastConfig,
+    policy_ref: String,
+    preemptive_scaled: RwLock<HashMap<String, DateTime<Utc>>>,
 }
 
 impl MetaCognitiveAgent {
     pub async fn new(
-        llm: Arc<dyn LlmClient>,
+        llm: LlmClientHandle,
         event_bus: EventBus,
         config: MetaCognitiveConfig,
+        forecasting: ForecastConfig,
+        policy_ref: String,
     ) -> Result<Self> {
         let kube_client = KubeClient::try_default()
             .await
             .map_err(|e| RecistError::KubeError(e))?;
 
         Ok(Self {
+            availability: AvailabilityGuard::new(kube_client.clone()),
+            workloads: WorkloadResolver::new(kube_client.clone()),
             kube_client,
             llm,
             event_bus,
             config,
+            forecasting,
+            policy_ref,
+            preemptive_scaled: RwLock::new(HashMap::new()),
         })
     }
 
@@ -60,7 +89,16 @@ impl MetaCognitiveAgent {
             namespace, pod_name, hypothesis.root_cause
         );
 
-        let strategies = self.generate_candidate_strategies(hypothesis);
+        let mut strategies = self.generate_candidate_strategies(hypothesis);
+        let config_map = if strategies.contains(&StrategyType::ConfigUpdate) {
+            self.config_update_target(namespace, pod_name).await
+        } else {
+            None
+        };
+        if config_map.is_none() {
+            strategies.retain(|strategy| *strategy != StrategyType::ConfigUpdate);
+        }
+        let llm = self.llm.get().await?;
 
         let micro_agents: Vec<_> = strategies
             .iter()
@@ -69,7 +107,7 @@ impl MetaCognitiveAgent {
                 MicroAgent::new(
                     s.clone(),
                     hypothesis.clone(),
-                    self.llm.clone(),
+                    llm.clone(),
                     self.config.max_reasoning_depth,
                 )
             })
@@ -82,18 +120,12 @@ impl MetaCognitiveAgent {
 
         let results: Vec<Result<MicroAgentResult>> = join_all(futures).await;
 
-        let mut best_result: Option<MicroAgentResult> = None;
+        let mut accepted = Vec::new();
         for result in results {
             match result {
                 Ok(r) => {
                     if r.confidence >= self.config.decision_threshold {
-                        match &best_result {
-                            None => best_result = Some(r),
-                            Some(current) if r.confidence > current.confidence => {
-                                best_result = Some(r);
-                            }
-                            _ => {}
-                        }
+                        accepted.push(r);
                     }
                 }
                 Err(e) => {
@@ -102,22 +134,74 @@ impl MetaCognitiveAgent {
             }
         }
 
-        let selected = best_result.ok_or_else(|| {
-            RecistError::HealingError("No strategy met confidence threshold".to_string())
-        })?;
-
-        info!(
-            "Selected strategy: {:?} with confidence {:.2}",
-            selected.strategy_type, selected.confidence
+        let selected = select_compatible(
+            &accepted,
+            self.config.max_composite_steps.max(1) as usize,
+            self.config.max_composite_risk_score,
         );
+        if selected.is_empty() {
+            return Err(RecistError::HealingError(
+                "No strategy met confidence threshold".to_string(),
+            ));
+        }
+
+        let workload = match self.workloads.resolve(namespace, pod_name).await {
+            Ok(workload) => workload,
+            Err(e) => {
+                warn!(
+                    "Failed to resolve workload of pod {}/{}: {}",
+                    namespace, pod_name, e
+                );
+                None
+            }
+        };
+
+        let actions: Vec<PlannedAction> = selected
+            .iter()
+            .flat_map(|r| {
+                self.create_actions_for_strategy(
+                    &r.strategy_type,
+                    namespace,
+                    pod_name,
+                    workload.as_ref(),
+                    config_map.as_deref(),
+                )
+            })
+            .collect();
 
-        let mut strategy =
-            SolutionStrategy::new(selected.strategy_type.clone(), selected.confidence);
+        let mut strategy = if selected.len() > 1 {
+            let steps: Vec<StrategyType> =
+                selected.iter().map(|r| r.strategy_type.clone()).collect();
+            let confidence = selected
+                .iter()
+                .map(|r| r.confidence)
+                .fold(f64::INFINITY, f64::min);
+
+            let mut strategy = SolutionStrategy::composite(&steps, confidence);
+            for action in sequence(actions) {
+                strategy.add_action(action);
+            }
 
-        let action = self.create_action_for_strategy(&selected.strategy_type, namespace, pod_name);
-        strategy.add_action(action);
+            info!(
+                "Selected composite strategy {:?} with confidence {:.2} and {} risk ({:.2})",
+                steps, confidence, strategy.risk_level, strategy.risk_score
+            );
+            strategy
+        } else {
+            info!(
+                "Selected strategy: {:?} with confidence {:.2}",
+                selected[0].strategy_type, selected[0].confidence
+            );
+
+            let mut strategy =
+                SolutionStrategy::new(selected[0].strategy_type.clone(), selected[0].confidence);
+            for action in actions {
+                strategy.add_action(action);
+            }
+            strategy
+        };
 
-        let rollback = self.create_rollback_plan(&selected.strategy_type, namespace, pod_name);
+        let rollback = self.create_rollback_plan(&strategy.actions);
         strategy.set_rollback_plan(rollback);
 
         Ok(strategy)
@@ -166,18 +250,18 @@ impl MetaCognitiveAgent {
         strategies
     }
 
-    fn create_action_for_strategy(
+    fn create_actions_for_strategy(
         &self,
         strategy: &StrategyType,
         namespace: &str,
         pod_name: &str,
-    ) -> PlannedAction {
+        workload: Option<&WorkloadRef>,
+        config_map: Option<&str>,
+    ) -> Vec<PlannedAction> {
         let (action_type, resource_type) = match strategy {
             StrategyType::PodRestart => (ActionType::PodRestart, ResourceType::Pod),
-            StrategyType::HorizontalScale => {
-                (ActionType::HorizontalScale, ResourceType::Deployment)
-            }
-            StrategyType::VerticalScale => (ActionType::VerticalScale, ResourceType::Deployment),
+            StrategyType::HorizontalScale => (ActionType::HorizontalScale, ResourceType::Pod),
+            StrategyType::VerticalScale => (ActionType::VerticalScale, ResourceType::Pod),
             StrategyType::ConfigUpdate => (ActionType::ConfigUpdate, ResourceType::ConfigMap),
             StrategyType::DependencyRestart => (ActionType::DependencyRestart, ResourceType::Pod),
             StrategyType::NetworkIsolation => {
@@ -186,44 +270,95 @@ impl MetaCognitiveAgent {
             StrategyType::Composite => (ActionType::PodRestart, ResourceType::Pod),
         };
 
-        PlannedAction {
-            action_type,
-            target: ActionTarget {
+        let target = if strategy.targets_workload() {
+            workload_target(namespace, pod_name, workload)
+        } else {
+            ActionTarget {
+                name: match (&resource_type, config_map) {
+                    (ResourceType::ConfigMap, Some(config_map)) => config_map.to_string(),
+                    _ => pod_name.to_string(),
+                },
                 resource_type,
-                name: pod_name.to_string(),
                 namespace: namespace.to_string(),
-            },
-            parameters: HashMap::new(),
-            order: 1,
-            depends_on: vec![],
+            }
+        };
+
+        let mut parameters = HashMap::new();
+        match action_type {
+            ActionType::HorizontalScale => {
+                parameters.insert(ADDITIONAL_REPLICAS_PARAM.to_string(), "2".to_string());
+            }
+            ActionType::ConfigUpdate => parameters.extend(
+                self.config
+                    .config_updates
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone())),
+            ),
+            _ => {}
         }
-    }
 
-    fn create_rollback_plan(
-        &self,
-        strategy: &StrategyType,
-        namespace: &str,
-        pod_name: &str,
-    ) -> RollbackPlan {
-        let action_type = match strategy {
-            StrategyType::HorizontalScale | StrategyType::VerticalScale => {
-                RollbackActionType::RestoreReplicas
-            }
-            StrategyType::ConfigUpdate => RollbackActionType::RestoreConfig,
-            StrategyType::NetworkIsolation => RollbackActionType::DeleteNetworkPolicy,
-            _ => RollbackActionType::RestartPod,
-        };
+        let mut actions = vec![PlannedAction {
+            action_type,
+            target,
+            parameters,
+            order: 1,
+            depends_on: vec![],
+        }];
 
-        RollbackPlan {
-            actions: vec![RollbackAction {
-                action_type,
+        // Pods only pick up the updated ConfigMap once they are recreated.
+        if *strategy == StrategyType::ConfigUpdate {
+            actions.push(PlannedAction {
+                action_type: ActionType::PodRestart,
                 target: ActionTarget {
                     resource_type: ResourceType::Pod,
                     name: pod_name.to_string(),
                     namespace: namespace.to_string(),
                 },
-                original_state: "{}".to_string(),
-            }],
+                parameters: HashMap::new(),
+                order: 2,
+                depends_on: vec![1],
+            });
+        }
+
+        actions
+    }
+
+    fn create_rollback_plan(&self, actions: &[PlannedAction]) -> RollbackPlan {
+        let actions = actions
+            .iter()
+            .filter_map(|action| {
+                let (action_type, target) = match action.action_type {
+                    ActionType::HorizontalScale => {
+                        (RollbackActionType::RestoreReplicas, action.target.clone())
+                    }
+                    ActionType::VerticalScale => {
+                        (RollbackActionType::RestoreResources, action.target.clone())
+                    }
+                    ActionType::ConfigUpdate => {
+                        (RollbackActionType::RestoreConfig, action.target.clone())
+                    }
+                    ActionType::NetworkIsolation => (
+                        RollbackActionType::DeleteNetworkPolicy,
+                        ActionTarget {
+                            resource_type: ResourceType::NetworkPolicy,
+                            name: format!("recist-isolate-{}", action.target.name),
+                            namespace: action.target.namespace.clone(),
+                        },
+                    ),
+                    _ => return None,
+                };
+
+                Some(RollbackAction {
+                    order: action.order,
+                    action_type,
+                    target,
+                    original_state: "{}".to_string(),
+                })
+            })
+            .collect();
+
+        RollbackPlan {
+            actions,
             timeout_seconds: 60,
         }
     }
@@ -233,56 +368,226 @@ impl MetaCognitiveAgent {
         strategy: &SolutionStrategy,
         namespace: &str,
         pod_name: &str,
-    ) -> Result<ActionResult> {
+    ) -> Result<Vec<ActionResult>> {
         info!(
-            "Executing strategy {:?} for {}/{}",
-            strategy.strategy_type, namespace, pod_name
+            "Executing strategy {:?} with {} actions for {}/{}",
+            strategy.strategy_type,
+            strategy.actions.len(),
+            namespace,
+            pod_name
         );
 
-        let start = std::time::Instant::now();
+        let mut results = Vec::new();
+        for wave in execution_waves(&strategy.actions)? {
+            let wave_results =
+                join_all(wave.into_iter().map(|action| self.execute_action(action))).await;
 
-        let result = match strategy.strategy_type {
-            StrategyType::PodRestart => self.execute_pod_restart(namespace, pod_name).await,
-            StrategyType::HorizontalScale => {
-                self.execute_horizontal_scale(namespace, pod_name, 2).await
+            let failed = wave_results.iter().any(|result| !result.success);
+            results.extend(wave_results);
+
+            if failed {
+                warn!(
+                    "Stopping strategy {:?} for {}/{} after a failed action",
+                    strategy.strategy_type, namespace, pod_name
+                );
+                break;
             }
-            StrategyType::VerticalScale => self.execute_vertical_scale(namespace, pod_name).await,
-            StrategyType::NetworkIsolation => Ok(()),
-            _ => self.execute_pod_restart(namespace, pod_name).await,
+        }
+
+        Ok(results)
+    }
+
+    async fn execute_action(&self, action: &PlannedAction) -> ActionResult {
+        let start = std::time::Instant::now();
+        let timeout = std::time::Duration::from_secs(self.config.action_timeout_seconds);
+
+        let result = match tokio::time::timeout(timeout, self.apply_action(action)).await {
+            Ok(result) => result,
+            Err(_) => Err(RecistError::Timeout(format!(
+                "{} of {} did not complete within {:?}",
+                action.action_type, action.target.name, timeout
+            ))),
         };
 
         let duration = start.elapsed();
 
-        match result {
-            Ok(()) => {
+        let mut rollback_data = None;
+        let (success, message, refusal_reason) = match result {
+            Ok(original_state) => {
+                rollback_data = original_state.and_then(|state| serde_json::to_string(&state).ok());
                 info!(
-                    "Strategy {:?} executed successfully in {:?}",
-                    strategy.strategy_type, duration
+                    "Action {} {} on {}/{} completed in {:?}",
+                    action.order,
+                    action.action_type,
+                    action.target.namespace,
+                    action.target.name,
+                    duration
                 );
-                Ok(ActionResult {
-                    action_type: strategy.strategy_type.to_action_type(),
-                    success: true,
-                    message: format!("Strategy executed successfully in {:?}", duration),
-                    executed_at: Utc::now(),
-                    duration_ms: duration.as_millis() as i64,
-                    rollback_data: None,
-                })
+                (
+                    true,
+                    format!("{} completed in {:?}", action.action_type, duration),
+                    None,
+                )
+            }
+            Err(RecistError::ActionRefused(reason)) => {
+                warn!(
+                    "Action {} {} refused: {}",
+                    action.order, action.action_type, reason
+                );
+                (
+                    false,
+                    format!("{} refused: {}", action.action_type, reason),
+                    Some(reason),
+                )
             }
             Err(e) => {
-                error!("Strategy {:?} failed: {}", strategy.strategy_type, e);
-                Ok(ActionResult {
-                    action_type: strategy.strategy_type.to_action_type(),
-                    success: false,
-                    message: format!("Strategy failed: {}", e),
-                    executed_at: Utc::now(),
-                    duration_ms: duration.as_millis() as i64,
-                    rollback_data: None,
-                })
+                error!(
+                    "Action {} {} failed: {}",
+                    action.order, action.action_type, e
+                );
+                (false, format!("{} failed: {}", action.action_type, e), None)
+            }
+        };
+
+        ActionResult {
+            action_type: action.action_type.clone(),
+            order: action.order,
+            success,
+            message,
+            executed_at: Utc::now(),
+            duration_ms: duration.as_millis() as i64,
+            rollback_data,
+            refusal_reason,
+            rolled_back: false,
+        }
+    }
+
+    async fn apply_action(&self, action: &PlannedAction) -> Result<Option<OriginalState>> {
+        let namespace = action.target.namespace.as_str();
+
+        match action.action_type {
+            ActionType::PodRestart | ActionType::DependencyRestart => {
+                self.execute_pod_restart(namespace, &action.target.name)
+                    .await?;
+                Ok(None)
+            }
+            ActionType::HorizontalScale => {
+                let additional_replicas = action
+                    .parameters
+                    .get(ADDITIONAL_REPLICAS_PARAM)
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(2);
+                let max_replicas = action
+                    .parameters
+                    .get(MAX_REPLICAS_PARAM)
+                    .and_then(|value| value.parse().ok());
+                let workload = self.target_workload(&action.target).await?;
+                self.execute_horizontal_scale(
+                    namespace,
+                    workload,
+                    additional_replicas,
+                    max_replicas,
+                )
+                .await
+                .map(Some)
+            }
+            ActionType::VerticalScale => {
+                let workload = self.target_workload(&action.target).await?;
+                self.execute_vertical_scale(namespace, workload)
+                    .await
+                    .map(Some)
+            }
+            ActionType::ConfigUpdate => {
+                if action.target.resource_type != ResourceType::ConfigMap
+                    || action.parameters.is_empty()
+                {
+                    return Err(RecistError::HealingError(format!(
+                        "{} of {}/{} has no ConfigMap changes to apply",
+                        action.action_type, namespace, action.target.name
+                    )));
+                }
+                self.execute_config_update(namespace, &action.target.name, &action.parameters)
+                    .await
+                    .map(Some)
+            }
+            ActionType::NetworkIsolation | ActionType::NetworkRestore => {
+                debug!(
+                    "{} of {}/{} is handled by the containment agent",
+                    action.action_type, namespace, action.target.name
+                );
+                Ok(None)
             }
         }
     }
 
+    async fn config_update_target(&self, namespace: &str, pod_name: &str) -> Option<String> {
+        if self.config.config_updates.is_empty() {
+            debug!(
+                "No configUpdates configured, skipping ConfigUpdate for {}/{}",
+                namespace, pod_name
+            );
+            return None;
+        }
+
+        let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
+        match pods.get(pod_name).await {
+            Ok(pod) => {
+                let config_map = referenced_config_map(&pod);
+                if config_map.is_none() {
+                    debug!(
+                        "Pod {}/{} references no ConfigMap, skipping ConfigUpdate",
+                        namespace, pod_name
+                    );
+                }
+                config_map
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to get pod {}/{} to resolve its ConfigMap: {}",
+                    namespace, pod_name, e
+                );
+                None
+            }
+        }
+    }
+
+    async fn execute_config_update(
+        &self,
+        namespace: &str,
+        config_map: &str,
+        data: &HashMap<String, String>,
+    ) -> Result<OriginalState> {
+        let config_maps: Api<ConfigMap> = Api::namespaced(self.kube_client.clone(), namespace);
+
+        let current = config_maps.get(config_map).await?.data.unwrap_or_default();
+        let original_state = config_snapshot(config_map, &current, data);
+
+        let patch = serde_json::json!({
+            "data": data
+        });
+
+        config_maps
+            .patch(config_map, &PatchParams::default(), &Patch::Merge(&patch))
+            .await?;
+
+        info!(
+            "Updated keys {:?} of ConfigMap {}/{}",
+            data.keys().collect::<Vec<_>>(),
+            namespace,
+            config_map
+        );
+
+        Ok(original_state)
+    }
+
     async fn execute_pod_restart(&self, namespace: &str, pod_name: &str) -> Result<()> {
+        match self.availability.assess(namespace, pod_name).await? {
+            Availability::Allowed => {}
+            Availability::Degraded(reason) | Availability::Refused(reason) => {
+                return Err(RecistError::ActionRefused(reason));
+            }
+        }
+
         let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
 
         pods.delete(pod_name, &DeleteParams::default())
@@ -293,35 +598,64 @@ impl MetaCognitiveAgent {
         Ok(())
     }
 
+    async fn owning_workload(&self, namespace: &str, pod_name: &str) -> Result<WorkloadRef> {
+        self.workloads
+            .resolve(namespace, pod_name)
+            .await?
+            .ok_or_else(|| {
+                RecistError::HealingError(format!(
+                    "Pod {}/{} is not managed by a workload controller",
+                    namespace, pod_name
+                ))
+            })
+    }
+
+    async fn target_workload(&self, target: &ActionTarget) -> Result<WorkloadRef> {
+        match target.workload() {
+            Some(workload) => Ok(workload),
+            None if target.resource_type == ResourceType::Pod => {
+                self.owning_workload(&target.namespace, &target.name).await
+            }
+            None => Err(RecistError::HealingError(format!(
+                "{:?} {}/{} is not a workload",
+                target.resource_type, target.namespace, target.name
+            ))),
+        }
+    }
+
     async fn execute_horizontal_scale(
         &self,
         namespace: &str,
-        pod_name: &str,
+        workload: WorkloadRef,
         additional_replicas: i32,
-    ) -> Result<()> {
-        let deployment_name = pod_name
-            .rsplit('-')
-            .skip(2)
-            .collect::<Vec<_>>()
-            .into_iter()
-            .rev()
-            .collect::<Vec<_>>()
-            .join("-");
-
-        let deployments: Api<Deployment> = Api::namespaced(self.kube_client.clone(), namespace);
+        max_replicas: Option<i32>,
+    ) -> Result<OriginalState> {
+        if !ResourceType::from_kind(&workload.kind).is_some_and(|t| t.is_scalable()) {
+            return Err(RecistError::HealingError(format!(
+                "{} cannot be scaled horizontally",
+                workload
+            )));
+        }
 
-        let deployment = deployments
-            .get(&deployment_name)
-            .await
-            .map_err(|e| RecistError::KubeError(e))?;
+        let api = self.workloads.api(namespace, &workload);
 
-        let current_replicas = deployment
+        let current_replicas = api
+            .get_scale(&workload.name)
+            .await?
             .spec
-            .as_ref()
             .and_then(|s| s.replicas)
             .unwrap_or(1);
 
-        let new_replicas = current_replicas + additional_replicas;
+        let new_replicas = match max_replicas {
+            Some(max_replicas) if current_replicas >= max_replicas => {
+                return Err(RecistError::ActionRefused(format!(
+                    "{} already runs {} replicas (maximum {})",
+                    workload, current_replicas, max_replicas
+                )));
+            }
+            Some(max_replicas) => (current_replicas + additional_replicas).min(max_replicas),
+            None => current_replicas + additional_replicas,
+        };
 
         let patch = serde_json::json!({
             "spec": {
@@ -329,41 +663,60 @@ impl MetaCognitiveAgent {
             }
         });
 
-        deployments
-            .patch(
-                &deployment_name,
-                &PatchParams::default(),
-                &Patch::Merge(&patch),
-            )
-            .await
-            .map_err(|e| RecistError::KubeError(e))?;
+        api.patch_scale(
+            &workload.name,
+            &PatchParams::default(),
+            &Patch::Merge(&patch),
+        )
+        .await?;
 
         info!(
-            "Scaled deployment {}/{} from {} to {} replicas",
-            namespace, deployment_name, current_replicas, new_replicas
+            "Scaled {} in {} from {} to {} replicas",
+            workload, namespace, current_replicas, new_replicas
         );
 
-        Ok(())
+        Ok(OriginalState::Replicas {
+            workload,
+            replicas: current_replicas,
+        })
     }
 
-    async fn execute_vertical_scale(&self, namespace: &str, pod_name: &str) -> Result<()> {
-        let deployment_name = pod_name
-            .rsplit('-')
-            .skip(2)
-            .collect::<Vec<_>>()
-            .into_iter()
-            .rev()
-            .collect::<Vec<_>>()
-            .join("-");
+    async fn execute_vertical_scale(
+        &self,
+        namespace: &str,
+        workload: WorkloadRef,
+    ) -> Result<OriginalState> {
+        if !matches!(
+            ResourceType::from_kind(&workload.kind),
+            Some(
+                ResourceType::Deployment
+                    | ResourceType::StatefulSet
+                    | ResourceType::DaemonSet
+                    | ResourceType::ReplicaSet
+            )
+        ) {
+            return Err(RecistError::HealingError(format!(
+                "Vertical scaling is not supported for {}",
+                workload
+            )));
+        }
 
-        let deployments: Api<Deployment> = Api::namespaced(self.kube_client.clone(), namespace);
+        let api = self.workloads.api(namespace, &workload);
+        let object = api.get(&workload.name).await?;
+        let container = &object.data["spec"]["template"]["spec"]["containers"][0];
+        let container_name = container["name"]
+            .as_str()
+            .ok_or_else(|| {
+                RecistError::HealingError(format!("{} has no containers to resize", workload))
+            })?
+            .to_string();
 
         let patch = serde_json::json!({
             "spec": {
                 "template": {
                     "spec": {
                         "containers": [{
-                            "name": deployment_name.clone(),
+                            "name": container_name,
                             "resources": {
                                 "limits": {
                                     "cpu": "1000m",
@@ -380,18 +733,316 @@ impl MetaCognitiveAgent {
             }
         });
 
-        deployments
-            .patch(
-                &deployment_name,
-                &PatchParams::default(),
-                &Patch::Strategic(&patch),
-            )
+        api.patch(
+            &workload.name,
+            &PatchParams::default(),
+            &Patch::Strategic(&patch),
+        )
+        .await?;
+
+        info!(
+            "Updated resource limits of container {} in {} ({})",
+            container_name, workload, namespace
+        );
+
+        Ok(OriginalState::Resources {
+            workload,
+            container: container_name,
+            resources: container["resources"].clone(),
+        })
+    }
+
+    pub async fn execute_rollback(&self, plan: &RollbackPlan) -> Vec<u32> {
+        let timeout = std::time::Duration::from_secs(plan.timeout_seconds);
+        let mut rolled_back = Vec::new();
+
+        let rollback = async {
+            for action in plan.actions.iter().rev() {
+                let original_state = match action.captured_state() {
+                    Some(original_state) => Some(original_state),
+                    None if matches!(
+                        action.action_type,
+                        RollbackActionType::DeleteNetworkPolicy | RollbackActionType::RestartPod
+                    ) =>
+                    {
+                        None
+                    }
+                    None => {
+                        debug!(
+                            "No state captured for action {}, nothing to roll back",
+                            action.order
+                        );
+                        continue;
+                    }
+                };
+
+                match self.apply_rollback(action, original_state).await {
+                    Ok(()) => {
+                        info!(
+                            "Rolled back action {} with {:?} on {}/{}",
+                            action.order,
+                            action.action_type,
+                            action.target.namespace,
+                            action.target.name
+                        );
+                        rolled_back.push(action.order);
+                    }
+                    Err(e) => {
+                        error!(
+                            "Rollback {:?} of action {} failed: {}",
+                            action.action_type, action.order, e
+                        );
+                    }
+                }
+            }
+        };
+
+        if tokio::time::timeout(timeout, rollback).await.is_err() {
+            error!("Rollback did not complete within {:?}", timeout);
+        }
+
+        rolled_back
+    }
+
+    async fn apply_rollback(
+        &self,
+        action: &RollbackAction,
+        original_state: Option<OriginalState>,
+    ) -> Result<()> {
+        let namespace = action.target.namespace.as_str();
+
+        match (&action.action_type, original_state) {
+            (
+                RollbackActionType::RestoreReplicas,
+                Some(OriginalState::Replicas { workload, replicas }),
+            ) => {
+                let patch = serde_json::json!({
+                    "spec": {
+                        "replicas": replicas
+                    }
+                });
+                self.workloads
+                    .api(namespace, &workload)
+                    .patch_scale(
+                        &workload.name,
+                        &PatchParams::default(),
+                        &Patch::Merge(&patch),
+                    )
+                    .await?;
+            }
+            (
+                RollbackActionType::RestoreResources,
+                Some(OriginalState::Resources {
+                    workload,
+                    container,
+                    resources,
+                }),
+            ) => {
+                let patch = serde_json::json!({
+                    "spec": {
+                        "template": {
+                            "spec": {
+                                "containers": [{
+                                    "name": container,
+                                    "resources": restore_resources_patch(&resources)
+                                }]
+                            }
+                        }
+                    }
+                });
+                self.workloads
+                    .api(namespace, &workload)
+                    .patch(
+                        &workload.name,
+                        &PatchParams::default(),
+                        &Patch::Strategic(&patch),
+                    )
+                    .await?;
+            }
+            (RollbackActionType::RestoreConfig, Some(OriginalState::Config { name, data })) => {
+                let config_maps: Api<ConfigMap> =
+                    Api::namespaced(self.kube_client.clone(), namespace);
+                let patch = restore_config_patch(&data);
+                config_maps
+                    .patch(&name, &PatchParams::default(), &Patch::Merge(&patch))
+                    .await?;
+            }
+            (RollbackActionType::DeleteNetworkPolicy, _) => {
+                let policies: Api<NetworkPolicy> =
+                    Api::namespaced(self.kube_client.clone(), namespace);
+                match policies
+                    .delete(&action.target.name, &DeleteParams::default())
+                    .await
+                {
+                    Ok(_) => {}
+                    Err(kube::Error::Api(ae)) if ae.code == 404 => {
+                        debug!(
+                            "NetworkPolicy {}/{} not found, already removed",
+                            namespace, action.target.name
+                        );
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            (RollbackActionType::RestartPod, _) => {
+                let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
+                pods.delete(&action.target.name, &DeleteParams::default())
+                    .await?;
+            }
+            (action_type, original_state) => {
+                return Err(RecistError::HealingError(format!(
+                    "Captured state {:?} cannot be restored by {:?}",
+                    original_state, action_type
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn preemptive_scale(
+        &self,
+        payload: &ProactiveWarningPayload,
+        pod_name: &str,
+    ) -> Result<()> {
+        let namespace = payload.namespace.as_str();
+        let workload = self.owning_workload(namespace, pod_name).await?;
+        let key = format!("{}/{}", namespace, workload);
+
+        let now = Utc::now();
+        let cooldown = ChronoDuration::minutes(self.forecasting.horizon_minutes as i64);
+        {
+            let mut scaled = self.preemptive_scaled.write().await;
+            scaled.retain(|_, at| now - *at < cooldown);
+            if scaled.contains_key(&key) {
+                debug!(
+                    "{} was pre-emptively scaled within the last {} minutes",
+                    key, self.forecasting.horizon_minutes
+                );
+                return Ok(());
+            }
+            scaled.insert(key.clone(), now);
+        }
+
+        match self
+            .availability
+            .assess_scale_up(namespace, &workload)
+            .await?
+        {
+            Availability::Allowed => {}
+            Availability::Degraded(reason) | Availability::Refused(reason) => {
+                info!("Skipping pre-emptive scaling of {}: {}", key, reason);
+                return Ok(());
+            }
+        }
+
+        info!(
+            "Pre-emptively scaling {} for pod {}: {}",
+            key, pod_name, payload.message
+        );
+
+        let action = PlannedAction {
+            action_type: ActionType::HorizontalScale,
+            target: workload_target(namespace, pod_name, Some(&workload)),
+            parameters: HashMap::from([
+                (ADDITIONAL_REPLICAS_PARAM.to_string(), "1".to_string()),
+                (
+                    MAX_REPLICAS_PARAM.to_string(),
+                    self.forecasting.max_replicas.to_string(),
+                ),
+            ]),
+            order: 1,
+            depends_on: vec![],
+        };
+        let result = self.execute_action(&action).await;
+
+        self.record_preemptive_scale(payload, pod_name, &action, &result)
             .await
-            .map_err(|e| RecistError::KubeError(e))?;
+    }
+
+    async fn record_preemptive_scale(
+        &self,
+        payload: &ProactiveWarningPayload,
+        pod_name: &str,
+        action: &PlannedAction,
+        result: &ActionResult,
+    ) -> Result<()> {
+        let namespace = payload.namespace.as_str();
+        let name = format!(
+            "{}-preempt-{}",
+            pod_name,
+            &Uuid::new_v4().simple().to_string()[..8]
+        );
+        let (policy_namespace, policy_name) = self
+            .policy_ref
+            .split_once('/')
+            .unwrap_or(("", self.policy_ref.as_str()));
+
+        let mut healing_event = HealingEvent::new(
+            &name,
+            HealingEventSpec {
+                policy_ref: self.policy_ref.clone(),
+                target_pod: pod_name.to_string(),
+                target_namespace: namespace.to_string(),
+                trigger_reason: payload.reason.clone().unwrap_or(TriggerReason::Unknown),
+                trigger_metrics: None,
+            },
+        );
+        healing_event.metadata.namespace = Some(namespace.to_string());
+        healing_event.metadata.labels = Some(BTreeMap::from([
+            (POLICY_NAME_LABEL.to_string(), policy_name.to_string()),
+            (
+                POLICY_NAMESPACE_LABEL.to_string(),
+                policy_namespace.to_string(),
+            ),
+        ]));
+
+        let api: Api<HealingEvent> = Api::namespaced(self.kube_client.clone(), namespace);
+        api.create(&PostParams::default(), &healing_event).await?;
+
+        let executed_at = result.executed_at.to_rfc3339();
+        let status = HealingEventStatus {
+            phase: if result.success {
+                HealingPhase::Completed
+            } else {
+                HealingPhase::Failed
+            },
+            start_time: Some(executed_at.clone()),
+            end_time: Some(executed_at.clone()),
+            duration_ms: Some(result.duration_ms),
+            applied_actions: vec![AppliedAction {
+                action_type: action.action_type.clone(),
+                timestamp: executed_at,
+                result: if result.success {
+                    AppliedActionResult::Success
+                } else {
+                    AppliedActionResult::Failed
+                },
+                details: Some(format!(
+                    "{:?} {}/{}: {}",
+                    action.target.resource_type,
+                    action.target.namespace,
+                    action.target.name,
+                    result.message
+                )),
+                rollback_info: result.rollback_data.clone(),
+            }],
+            outcome: Some(HealingOutcome {
+                success: result.success,
+                message: format!("Pre-emptive scale-up: {}", payload.message),
+                verification_method: None,
+                metrics_after: None,
+            }),
+            ..Default::default()
+        };
+
+        let patch = serde_json::json!({ "status": status });
+        api.patch_status(&name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await?;
 
         info!(
-            "Updated resource limits for deployment {}/{}",
-            namespace, deployment_name
+            "Recorded pre-emptive scale-up of {}/{} in HealingEvent {}/{}",
+            action.target.namespace, action.target.name, namespace, name
         );
 
         Ok(())
@@ -442,6 +1093,42 @@ impl MetaCognitiveAgent {
     }
 }
 
+fn referenced_config_map(pod: &Pod) -> Option<String> {
+    let spec = pod.spec.as_ref()?;
+    let from_env = spec
+        .containers
+        .iter()
+        .flat_map(|container| container.env_from.iter().flatten())
+        .find_map(|source| source.config_map_ref.as_ref()?.name.clone());
+    let from_volumes = || {
+        spec.volumes
+            .iter()
+            .flatten()
+            .find_map(|volume| volume.config_map.as_ref()?.name.clone())
+    };
+
+    from_env.or_else(from_volumes)
+}
+
+fn workload_target(
+    namespace: &str,
+    pod_name: &str,
+    workload: Option<&WorkloadRef>,
+) -> ActionTarget {
+    match workload.and_then(|w| ResourceType::from_kind(&w.kind).map(|t| (t, w))) {
+        Some((resource_type, workload)) => ActionTarget {
+            resource_type,
+            name: workload.name.clone(),
+            namespace: namespace.to_string(),
+        },
+        None => ActionTarget {
+            resource_type: ResourceType::Pod,
+            name: pod_name.to_string(),
+            namespace: namespace.to_string(),
+        },
+    }
+}
+
 #[async_trait]
 impl Agent for MetaCognitiveAgent {
     fn agent_type(&self) -> AgentType {
@@ -459,7 +1146,10 @@ impl Agent for MetaCognitiveAgent {
     }
 
     fn subscribe_to(&self) -> Vec<AgentEventType> {
-        vec![AgentEventType::DiagnosisComplete]
+        vec![
+            AgentEventType::DiagnosisComplete,
+            AgentEventType::ProactiveWarning,
+        ]
     }
 }
 
@@ -468,45 +1158,145 @@ impl EventHandler for MetaCognitiveAgent {
     async fn handle_event(&self, event: AgentEvent) -> Result<Option<AgentEvent>> {
         match &event.payload {
             EventPayload::DiagnosisComplete(payload) => {
+                let context = &payload.context;
+                let namespace = context.namespace.as_str();
+                let pod_name = context.pod_name.as_str();
+
                 info!(
-                    "Received diagnosis, determining strategy for correlation {}",
-                    event.correlation_id
+                    "Received diagnosis for {}/{}, determining strategy for correlation {}",
+                    namespace, pod_name, event.correlation_id
                 );
 
-                let namespace = "default";
-                let pod_name = "unknown";
-
                 match self
                     .determine_strategy(&payload.hypothesis, namespace, pod_name)
                     .await
                 {
-                    Ok(strategy) => {
-                        let result = self
-                            .execute_strategy(&strategy, namespace, pod_name)
-                            .await?;
-
-                        let success = if result.success {
-                            self.verify_healing(namespace, pod_name)
-                                .await
-                                .unwrap_or(false)
-                        } else {
-                            false
-                        };
+                    Ok(mut strategy) => {
+                        let (mut results, invalid_plan) =
+                            match self.execute_strategy(&strategy, namespace, pod_name).await {
+                                Ok(results) => (results, None),
+                                Err(e) => {
+                                    error!(
+                                        "Strategy {} for {}/{} cannot be executed: {}",
+                                        strategy.strategy_type, namespace, pod_name, e
+                                    );
+                                    (Vec::new(), Some(e))
+                                }
+                            };
+                        strategy.capture_rollback_state(&results);
+
+                        let failed = results.iter().find(|result| !result.success);
+                        let (success, mut message, refusal_reason, verification_started_at) =
+                            match (invalid_plan, failed) {
+                                (Some(e), _) => (
+                                    false,
+                                    format!(
+                                        "Strategy {} was not executed: {}",
+                                        strategy.strategy_type, e
+                                    ),
+                                    None,
+                                    None,
+                                ),
+                                (None, Some(result)) => (
+                                    false,
+                                    result.message.clone(),
+                                    result.refusal_reason.clone(),
+                                    None,
+                                ),
+                                (None, None) => {
+                                    let verification_started_at = Utc::now();
+                                    let verified = self
+                                        .verify_healing(namespace, pod_name)
+                                        .await
+                                        .unwrap_or(false);
+                                    (
+                                        verified,
+                                        format!(
+                                            "Strategy {} executed {} actions",
+                                            strategy.strategy_type,
+                                            results.len()
+                                        ),
+                                        None,
+                                        Some(verification_started_at),
+                                    )
+                                }
+                            };
+
+                        if !success && !results.is_empty() {
+                            if let Some(plan) = &strategy.rollback_plan {
+                                let rolled_back = self.execute_rollback(plan).await;
+                                for result in &mut results {
+                                    result.rolled_back = rolled_back.contains(&result.order);
+                                }
+                                if !rolled_back.is_empty() {
+                                    message = format!(
+                                        "{} (rolled back {} actions)",
+                                        message,
+                                        rolled_back.len()
+                                    );
+                                }
+                            }
+                        }
 
                         let response = AgentEvent::healing_complete(
                             event.correlation_id,
-                            strategy,
-                            success,
-                            result.message,
+                            HealingCompletePayload {
+                                context: context.clone(),
+                                hypothesis: payload.hypothesis.clone(),
+                                strategy,
+                                success,
+                                message,
+                                refusal_reason,
+                                action_results: results,
+                                verification_started_at,
+                            },
                         );
 
                         return Ok(Some(response));
                     }
                     Err(e) => {
-                        error!("Failed to determine strategy: {}", e);
+                        error!(
+                            "Failed to determine strategy for {}/{}: {}",
+                            namespace, pod_name, e
+                        );
+
+                        return Ok(Some(AgentEvent::healing_complete(
+                            event.correlation_id,
+                            HealingCompletePayload {
+                                context: context.clone(),
+                                hypothesis: payload.hypothesis.clone(),
+                                strategy: SolutionStrategy::composite(&[], 0.0),
+                                success: false,
+                                message: format!("No healing strategy was selected: {}", e),
+                                refusal_reason: None,
+                                action_results: Vec::new(),
+                                verification_started_at: None,
+                            },
+                        )));
                     }
                 }
             }
+            EventPayload::ProactiveWarning(payload) => {
+                let pod_name = match &payload.pod_name {
+                    Some(pod_name) => pod_name,
+                    None => return Ok(None),
+                };
+
+                if payload.suggested_action.as_deref() != Some(PREEMPTIVE_SCALE_ACTION) {
+                    debug!(
+                        "Proactive warning for {}/{} has no pre-emptive action: {}",
+                        payload.namespace, pod_name, payload.message
+                    );
+                    return Ok(None);
+                }
+
+                if let Err(e) = self.preemptive_scale(payload, pod_name).await {
+                    warn!(
+                        "Pre-emptive scaling for {}/{} failed: {}",
+                        payload.namespace, pod_name, e
+                    );
+                }
+            }
             _ => {}
         }
 
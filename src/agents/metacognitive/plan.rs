@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+
+use crate::error::{RecistError, Result};
+use crate::models::PlannedAction;
+
+pub fn execution_waves(actions: &[PlannedAction]) -> Result<Vec<Vec<&PlannedAction>>> {
+    let orders: HashSet<u32> = actions.iter().map(|action| action.order).collect();
+    if orders.len() != actions.len() {
+        return Err(RecistError::ValidationError(
+            "Planned actions must have unique orders".to_string(),
+        ));
+    }
+
+    for action in actions {
+        if let Some(missing) = action.depends_on.iter().find(|d| !orders.contains(d)) {
+            return Err(RecistError::ValidationError(format!(
+                "Action {} depends on unknown action {}",
+                action.order, missing
+            )));
+        }
+    }
+
+    let mut remaining: Vec<&PlannedAction> = actions.iter().collect();
+    remaining.sort_by_key(|action| action.order);
+
+    let mut done = HashSet::new();
+    let mut waves = Vec::new();
+    while !remaining.is_empty() {
+        let (ready, blocked): (Vec<_>, Vec<_>) = remaining
+            .into_iter()
+            .partition(|action| action.depends_on.iter().all(|d| done.contains(d)));
+
+        if ready.is_empty() {
+            return Err(RecistError::ValidationError(format!(
+                "Dependency cycle between actions {:?}",
+                blocked
+                    .iter()
+                    .map(|action| action.order)
+                    .collect::<Vec<_>>()
+            )));
+        }
+
+        done.extend(ready.iter().map(|action| action.order));
+        waves.push(ready);
+        remaining = blocked;
+    }
+
+    Ok(waves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crd::ActionType;
+    use crate::models::{ActionTarget, ResourceType};
+    use std::collections::HashMap;
+
+    fn action(order: u32, depends_on: Vec<u32>) -> PlannedAction {
+        PlannedAction {
+            action_type: ActionType::PodRestart,
+            target: ActionTarget {
+                resource_type: ResourceType::Pod,
+                name: "web-1".to_string(),
+                namespace: "default".to_string(),
+            },
+            parameters: HashMap::new(),
+            order,
+            depends_on,
+        }
+    }
+
+    #[test]
+    fn test_execution_waves() {
+        let actions = vec![
+            action(3, vec![1, 2]),
+            action(1, vec![]),
+            action(2, vec![]),
+            action(4, vec![3]),
+        ];
+        let waves: Vec<Vec<u32>> = execution_waves(&actions)
+            .unwrap()
+            .iter()
+            .map(|wave| wave.iter().map(|action| action.order).collect())
+            .collect();
+        assert_eq!(waves, vec![vec![1, 2], vec![3], vec![4]]);
+
+        assert!(execution_waves(&[action(1, vec![2]), action(2, vec![1])]).is_err());
+        assert!(execution_waves(&[action(1, vec![5])]).is_err());
+        assert!(execution_waves(&[action(1, vec![]), action(1, vec![])]).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+
+use crate::models::OriginalState;
+
+const MANAGED_RESOURCES: [&str; 2] = ["cpu", "memory"];
+
+pub fn restore_resources_patch(original: &Value) -> Value {
+    let mut resources = Map::new();
+    for section in ["limits", "requests"] {
+        let mut values = original[section].as_object().cloned().unwrap_or_default();
+        for resource in MANAGED_RESOURCES {
+            values.entry(resource).or_insert(Value::Null);
+        }
+        resources.insert(section.to_string(), Value::Object(values));
+    }
+    Value::Object(resources)
+}
+
+pub fn config_snapshot(
+    name: &str,
+    current: &BTreeMap<String, String>,
+    data: &HashMap<String, String>,
+) -> OriginalState {
+    OriginalState::Config {
+        name: name.to_string(),
+        data: data
+            .keys()
+            .map(|key| (key.clone(), current.get(key).cloned()))
+            .collect(),
+    }
+}
+
+// Keys the update added are restored as null, which a merge patch removes.
+pub fn restore_config_patch(original: &BTreeMap<String, Option<String>>) -> Value {
+    serde_json::json!({ "data": original })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ActionTarget, ResourceType, RollbackAction, RollbackActionType};
+
+    fn merge_data(data: &mut BTreeMap<String, String>, patch: &Value) {
+        for (key, value) in patch["data"].as_object().unwrap() {
+            match value.as_str() {
+                Some(value) => {
+                    data.insert(key.clone(), value.to_string());
+                }
+                None => {
+                    data.remove(key);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_config_update_rolls_back_to_snapshot() {
+        let original: BTreeMap<String, String> = [
+            ("DB_POOL_SIZE".to_string(), "10".to_string()),
+            ("LOG_LEVEL".to_string(), "info".to_string()),
+        ]
+        .into_iter()
+        .collect();
+        let update = HashMap::from([
+            ("DB_POOL_SIZE".to_string(), "50".to_string()),
+            ("DB_POOL_TIMEOUT_SECONDS".to_string(), "30".to_string()),
+        ]);
+
+        let snapshot = config_snapshot("web-config", &original, &update);
+        let rollback = RollbackAction {
+            order: 1,
+            action_type: RollbackActionType::RestoreConfig,
+            target: ActionTarget {
+                resource_type: ResourceType::ConfigMap,
+                name: "web-config".to_string(),
+                namespace: "default".to_string(),
+            },
+            original_state: serde_json::to_string(&snapshot).unwrap(),
+        };
+
+        let mut data = original.clone();
+        merge_data(&mut data, &serde_json::json!({ "data": update }));
+        assert_eq!(data["DB_POOL_SIZE"], "50");
+        assert_eq!(data["DB_POOL_TIMEOUT_SECONDS"], "30");
+
+        let restored = match rollback.captured_state() {
+            Some(OriginalState::Config { name, data }) => {
+                assert_eq!(name, "web-config");
+                data
+            }
+            other => panic!("unexpected captured state {:?}", other),
+        };
+        merge_data(&mut data, &restore_config_patch(&restored));
+        assert_eq!(data, original);
+    }
+
+    #[test]
+    fn test_restore_resources_patch_clears_added_values() {
+        let original = serde_json::json!({
+            "requests": { "memory": "256Mi" }
+        });
+
+        let patch = restore_resources_patch(&original);
+        assert_eq!(patch["requests"]["memory"], "256Mi");
+        assert!(patch["requests"]["cpu"].is_null());
+        assert!(patch["limits"]["cpu"].is_null());
+        assert!(patch["limits"]["memory"].is_null());
+
+        let patch = restore_resources_patch(&Value::Null);
+        assert!(patch["limits"]["memory"].is_null());
+    }
+}
@@ -1,11 +1,29 @@
+mod anomaly;
+mod availability;
 mod containment;
 mod diagnosis;
+mod fault_state;
+mod forecast;
+mod incidents;
 mod knowledge;
 pub mod metacognitive;
+mod pod_status;
+mod supervisor;
+mod traffic;
 mod traits;
+mod workload;
 
+pub use anomaly::{AnomalyDetector, Baseline};
+pub use availability::{selector_matches, Availability, AvailabilityGuard};
 pub use containment::ContainmentAgent;
 pub use diagnosis::DiagnosisAgent;
+pub use fault_state::FaultStateTracker;
+pub use forecast::{Trend, TrendForecaster, PREEMPTIVE_SCALE_ACTION};
+pub use incidents::{Incident, IncidentCorrelator};
 pub use knowledge::KnowledgeAgent;
 pub use metacognitive::MetaCognitiveAgent;
+pub use pod_status::{PodStatusFault, PodStatusPoller};
+pub use supervisor::AgentSupervisor;
+pub use traffic::{route_weights, TrafficRouter};
 pub use traits::*;
+pub use workload::{controller_owner, workload_resource, WorkloadResolver};
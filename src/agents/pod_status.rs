@@ -0,0 +1,333 @@
+use chrono::{Duration as ChronoDuration, Utc};
+use k8s_openapi::api::core::v1::{ContainerStatus, Pod};
+use kube::ResourceExt;
+use std::collections::{HashMap, HashSet};
+use tokio::sync::RwLock;
+
+use crate::crd::TriggerReason;
+
+const PROBE_GRACE_SECONDS: i64 = 60;
+
+#[derive(Clone, Debug)]
+pub struct PodStatusFault {
+    pub pod_name: String,
+    pub reasons: Vec<TriggerReason>,
+    pub restart_count: i32,
+    pub details: Vec<String>,
+}
+
+// Polled from the containment check loop with the pods already listed there, so
+// restart deltas are measured per check interval rather than per watch event.
+pub struct PodStatusPoller {
+    restart_threshold: i32,
+    restart_counts: RwLock<HashMap<String, i32>>,
+}
+
+impl PodStatusPoller {
+    pub fn new(restart_threshold: i32) -> Self {
+        Self {
+            restart_threshold,
+            restart_counts: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub async fn evaluate(&self, namespace: &str, pods: &[Pod]) -> Vec<PodStatusFault> {
+        let mut restart_counts = self.restart_counts.write().await;
+        let prefix = format!("{}/", namespace);
+        let seen: HashSet<String> = pods
+            .iter()
+            .map(|pod| format!("{}{}", prefix, pod.name_any()))
+            .collect();
+        restart_counts.retain(|key, _| !key.starts_with(&prefix) || seen.contains(key));
+
+        let mut faults = Vec::new();
+
+        for pod in pods {
+            let pod_name = pod.name_any();
+            let statuses = container_statuses(pod);
+            let restart_count: i32 = statuses.iter().map(|s| s.restart_count).sum();
+
+            let previous = restart_counts.insert(format!("{}{}", prefix, pod_name), restart_count);
+            let restart_delta = previous.map(|p| restart_count - p).unwrap_or(0);
+
+            let mut reasons = Vec::new();
+            let mut details = Vec::new();
+
+            for status in &statuses {
+                let container = &status.name;
+                let state = status.state.as_ref();
+                let waiting_reason = state
+                    .and_then(|s| s.waiting.as_ref())
+                    .and_then(|w| w.reason.as_deref());
+                let terminated_reason = state
+                    .and_then(|s| s.terminated.as_ref())
+                    .and_then(|t| t.reason.as_deref());
+                let last_terminated_reason = status
+                    .last_state
+                    .as_ref()
+                    .and_then(|s| s.terminated.as_ref())
+                    .and_then(|t| t.reason.as_deref());
+
+                match waiting_reason {
+                    Some("CrashLoopBackOff") => {
+                        push_reason(&mut reasons, TriggerReason::CrashLoop);
+                        details.push(format!("container {} is in CrashLoopBackOff", container));
+                    }
+                    Some("ImagePullBackOff") | Some("ErrImagePull") => {
+                        push_reason(&mut reasons, TriggerReason::ImagePullBackOff);
+                        details.push(format!("container {} cannot pull its image", container));
+                    }
+                    _ => {}
+                }
+
+                let oom_killed = terminated_reason == Some("OOMKilled")
+                    || (restart_delta > 0 && last_terminated_reason == Some("OOMKilled"));
+                if oom_killed {
+                    push_reason(&mut reasons, TriggerReason::OomKilled);
+                    details.push(format!("container {} was OOMKilled", container));
+                }
+
+                let running_since = state
+                    .and_then(|s| s.running.as_ref())
+                    .and_then(|r| r.started_at.as_ref())
+                    .map(|t| t.0);
+                if let Some(started_at) = running_since {
+                    let past_grace =
+                        Utc::now() - started_at > ChronoDuration::seconds(PROBE_GRACE_SECONDS);
+                    if !status.ready && past_grace {
+                        push_reason(&mut reasons, TriggerReason::ProbeFailure);
+                        details.push(format!(
+                            "container {} is running but failing its readiness probe",
+                            container
+                        ));
+                    }
+                }
+            }
+
+            if restart_delta > 0 && !oom_or_crash(&reasons) {
+                let liveness_kill = statuses.iter().any(|s| {
+                    s.last_state
+                        .as_ref()
+                        .and_then(|ls| ls.terminated.as_ref())
+                        .map(|t| t.exit_code == 137 && t.reason.as_deref() == Some("Error"))
+                        .unwrap_or(false)
+                });
+                if liveness_kill {
+                    push_reason(&mut reasons, TriggerReason::ProbeFailure);
+                    details
+                        .push("container was killed after failing its liveness probe".to_string());
+                }
+            }
+
+            if self.restart_threshold > 0 && restart_delta >= self.restart_threshold {
+                push_reason(&mut reasons, TriggerReason::CrashLoop);
+                details.push(format!(
+                    "{} restarts since the previous check",
+                    restart_delta
+                ));
+            }
+
+            if !reasons.is_empty() {
+                faults.push(PodStatusFault {
+                    pod_name,
+                    reasons,
+                    restart_count,
+                    details,
+                });
+            }
+        }
+
+        faults
+    }
+}
+
+fn container_statuses(pod: &Pod) -> Vec<ContainerStatus> {
+    pod.status
+        .as_ref()
+        .and_then(|s| s.container_statuses.clone())
+        .unwrap_or_default()
+}
+
+fn push_reason(reasons: &mut Vec<TriggerReason>, reason: TriggerReason) {
+    if !reasons.contains(&reason) {
+        reasons.push(reason);
+    }
+}
+
+fn oom_or_crash(reasons: &[TriggerReason]) -> bool {
+    reasons
+        .iter()
+        .any(|r| matches!(r, TriggerReason::OomKilled | TriggerReason::CrashLoop))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{
+        ContainerState, ContainerStateRunning, ContainerStateTerminated, ContainerStateWaiting,
+        PodStatus,
+    };
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+    use kube::api::ObjectMeta;
+
+    fn pod(name: &str, status: ContainerStatus) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some(name.to_string()),
+                ..Default::default()
+            },
+            status: Some(PodStatus {
+                container_statuses: Some(vec![status]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn container(restart_count: i32) -> ContainerStatus {
+        ContainerStatus {
+            name: "app".to_string(),
+            ready: true,
+            restart_count,
+            state: Some(ContainerState {
+                running: Some(ContainerStateRunning {
+                    started_at: Some(Time(Utc::now())),
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn terminated(reason: &str, exit_code: i32) -> Option<ContainerState> {
+        Some(ContainerState {
+            terminated: Some(ContainerStateTerminated {
+                reason: Some(reason.to_string()),
+                exit_code,
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    fn reasons(faults: &[PodStatusFault], pod_name: &str) -> Vec<TriggerReason> {
+        faults
+            .iter()
+            .find(|f| f.pod_name == pod_name)
+            .map(|f| f.reasons.clone())
+            .unwrap_or_default()
+    }
+
+    #[tokio::test]
+    async fn test_termination_reasons() {
+        let poller = PodStatusPoller::new(0);
+
+        let mut crash_loop = container(4);
+        crash_loop.state = Some(ContainerState {
+            waiting: Some(ContainerStateWaiting {
+                reason: Some("CrashLoopBackOff".to_string()),
+                message: None,
+            }),
+            ..Default::default()
+        });
+        let mut image_pull = container(0);
+        image_pull.state = Some(ContainerState {
+            waiting: Some(ContainerStateWaiting {
+                reason: Some("ErrImagePull".to_string()),
+                message: None,
+            }),
+            ..Default::default()
+        });
+        let mut oom = container(1);
+        oom.state = terminated("OOMKilled", 137);
+
+        let faults = poller
+            .evaluate(
+                "default",
+                &[
+                    pod("crash", crash_loop),
+                    pod("pull", image_pull),
+                    pod("oom", oom),
+                    pod("healthy", container(0)),
+                ],
+            )
+            .await;
+
+        assert_eq!(reasons(&faults, "crash"), vec![TriggerReason::CrashLoop]);
+        assert_eq!(
+            reasons(&faults, "pull"),
+            vec![TriggerReason::ImagePullBackOff]
+        );
+        assert_eq!(reasons(&faults, "oom"), vec![TriggerReason::OomKilled]);
+        assert!(reasons(&faults, "healthy").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_restart_deltas() {
+        let poller = PodStatusPoller::new(3);
+
+        let mut first_oom = container(2);
+        first_oom.last_state = terminated("OOMKilled", 137);
+        assert!(poller
+            .evaluate("default", &[pod("web-1", first_oom.clone())])
+            .await
+            .is_empty());
+
+        let mut repeated_oom = first_oom.clone();
+        repeated_oom.restart_count = 3;
+        let faults = poller
+            .evaluate("default", &[pod("web-1", repeated_oom)])
+            .await;
+        assert_eq!(reasons(&faults, "web-1"), vec![TriggerReason::OomKilled]);
+
+        let faults = poller
+            .evaluate("default", &[pod("web-1", container(6))])
+            .await;
+        assert_eq!(reasons(&faults, "web-1"), vec![TriggerReason::CrashLoop]);
+        assert_eq!(faults[0].restart_count, 6);
+
+        assert!(poller
+            .evaluate("default", &[pod("web-1", container(6))])
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_probe_failures() {
+        let poller = PodStatusPoller::new(0);
+
+        let mut unready = container(0);
+        unready.ready = false;
+        unready.state = Some(ContainerState {
+            running: Some(ContainerStateRunning {
+                started_at: Some(Time(
+                    Utc::now() - ChronoDuration::seconds(PROBE_GRACE_SECONDS * 2),
+                )),
+            }),
+            ..Default::default()
+        });
+        let mut starting = container(0);
+        starting.ready = false;
+
+        let faults = poller
+            .evaluate(
+                "default",
+                &[pod("unready", unready), pod("starting", starting)],
+            )
+            .await;
+        assert_eq!(
+            reasons(&faults, "unready"),
+            vec![TriggerReason::ProbeFailure]
+        );
+        assert!(reasons(&faults, "starting").is_empty());
+
+        let mut liveness = container(0);
+        poller
+            .evaluate("default", &[pod("web-1", liveness.clone())])
+            .await;
+        liveness.restart_count = 1;
+        liveness.last_state = terminated("Error", 137);
+        let faults = poller.evaluate("default", &[pod("web-1", liveness)]).await;
+        assert_eq!(reasons(&faults, "web-1"), vec![TriggerReason::ProbeFailure]);
+    }
+}
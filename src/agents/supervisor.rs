@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::task::{JoinHandle, JoinSet};
+use tracing::{debug, error, info, warn};
+
+use super::traits::{Agent, EventHandler};
+use super::ContainmentAgent;
+use crate::error::Result;
+use crate::eventbus::{EventBus, EventFilter, FilteredReceiver};
+use crate::models::AgentType;
+
+const INITIAL_RESTART_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_RESTART_BACKOFF: Duration = Duration::from_secs(60);
+
+pub struct AgentSupervisor {
+    event_bus: EventBus,
+    agents: RwLock<Vec<Arc<dyn Agent>>>,
+    event_loops: RwLock<HashMap<AgentType, JoinHandle<()>>>,
+    check_loop: RwLock<Option<JoinHandle<()>>>,
+}
+
+impl AgentSupervisor {
+    pub fn new(event_bus: EventBus) -> Self {
+        Self {
+            event_bus,
+            agents: RwLock::new(Vec::new()),
+            event_loops: RwLock::new(HashMap::new()),
+            check_loop: RwLock::new(None),
+        }
+    }
+
+    pub async fn spawn_agent<A>(&self, agent: Arc<A>)
+    where
+        A: Agent + EventHandler + 'static,
+    {
+        let agent_type = agent.agent_type();
+        let event_bus = self.event_bus.clone();
+        let supervised = agent.clone();
+
+        let handle = tokio::spawn(supervise(agent_type.to_string(), move || {
+            run_event_loop(supervised.clone(), event_bus.clone())
+        }));
+
+        self.agents.write().await.push(agent);
+
+        if let Some(previous) = self.event_loops.write().await.insert(agent_type, handle) {
+            previous.abort();
+        }
+    }
+
+    pub async fn spawn_check_loop(&self, agent: Arc<ContainmentAgent>, namespaces: Vec<String>) {
+        info!(
+            "Starting containment check loop for namespaces {:?}",
+            namespaces
+        );
+
+        let handle = tokio::spawn(supervise("ContainmentCheckLoop".to_string(), move || {
+            let agent = agent.clone();
+            let namespaces = namespaces.clone();
+            async move {
+                agent.start().await?;
+                agent.run_check_loop(namespaces).await;
+                Ok(())
+            }
+        }));
+
+        if let Some(previous) = self.check_loop.write().await.replace(handle) {
+            previous.abort();
+        }
+    }
+
+    pub async fn check_loop_running(&self) -> bool {
+        self.check_loop
+            .read()
+            .await
+            .as_ref()
+            .is_some_and(|h| !h.is_finished())
+    }
+
+    pub async fn stop_check_loop(&self) {
+        if let Some(handle) = self.check_loop.write().await.take() {
+            info!("Stopping containment check loop");
+            handle.abort();
+        }
+    }
+
+    pub async fn running_loops(&self) -> usize {
+        let event_loops = self
+            .event_loops
+            .read()
+            .await
+            .values()
+            .filter(|h| !h.is_finished())
+            .count();
+        let check_loop = self
+            .check_loop
+            .read()
+            .await
+            .as_ref()
+            .map(|h| !h.is_finished())
+            .unwrap_or(false);
+
+        event_loops + usize::from(check_loop)
+    }
+
+    pub async fn shutdown(&self) {
+        info!("Shutting down agent supervisor");
+
+        if let Some(handle) = self.check_loop.write().await.take() {
+            handle.abort();
+        }
+
+        for (_, handle) in self.event_loops.write().await.drain() {
+            handle.abort();
+        }
+
+        for agent in self.agents.write().await.drain(..) {
+            if let Err(e) = agent.stop().await {
+                warn!("Failed to stop agent {}: {}", agent.agent_type(), e);
+            }
+        }
+    }
+}
+
+async fn supervise<F, Fut>(name: String, mut factory: F)
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    let mut backoff = INITIAL_RESTART_BACKOFF;
+
+    loop {
+        let mut task = JoinSet::new();
+        task.spawn(factory());
+
+        match task.join_next().await {
+            Some(Ok(Ok(()))) => {
+                info!("{} loop exited", name);
+                return;
+            }
+            Some(Ok(Err(e))) => {
+                error!("{} loop failed: {}", name, e);
+            }
+            Some(Err(e)) if e.is_cancelled() => {
+                debug!("{} loop cancelled", name);
+                return;
+            }
+            Some(Err(e)) => {
+                error!("{} loop crashed: {}", name, e);
+            }
+            None => return,
+        }
+
+        warn!("Restarting {} loop in {:?}", name, backoff);
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_RESTART_BACKOFF);
+    }
+}
+
+async fn run_event_loop<A>(agent: Arc<A>, event_bus: EventBus) -> Result<()>
+where
+    A: Agent + EventHandler + 'static,
+{
+    agent.start().await?;
+
+    let event_types = agent.subscribe_to();
+    let receiver = event_bus
+        .subscribe(agent.agent_type(), event_types.clone())
+        .await;
+    let mut receiver = FilteredReceiver::new(receiver, EventFilter::new(event_types));
+    let mut handlers = JoinSet::new();
+
+    loop {
+        tokio::select! {
+            event = receiver.recv() => {
+                let event = event?;
+                let agent = agent.clone();
+                let event_bus = event_bus.clone();
+
+                handlers.spawn(async move {
+                    let correlation_id = event.correlation_id;
+                    match agent.handle_event(event).await {
+                        Ok(Some(response)) => {
+                            if let Err(e) = event_bus.publish(response).await {
+                                error!(
+                                    "Agent {} failed to publish response for correlation {}: {}",
+                                    agent.agent_type(), correlation_id, e
+                                );
+                            }
+                        }
+                        Ok(None) => {}
+                        Err(e) => {
+                            error!(
+                                "Agent {} failed to handle event for correlation {}: {}",
+                                agent.agent_type(), correlation_id, e
+                            );
+                        }
+                    }
+                });
+            }
+            Some(result) = handlers.join_next() => {
+                if let Err(e) = result {
+                    if e.is_panic() {
+                        error!("Agent {} event handler panicked: {}", agent.agent_type(), e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AgentEvent, AgentEventType, FaultCluster};
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use uuid::Uuid;
+
+    struct EchoAgent {
+        starts: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl Agent for EchoAgent {
+        fn agent_type(&self) -> AgentType {
+            AgentType::Diagnosis
+        }
+
+        async fn start(&self) -> Result<()> {
+            self.starts.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+
+        async fn stop(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn subscribe_to(&self) -> Vec<AgentEventType> {
+            vec![AgentEventType::FaultDetected]
+        }
+    }
+
+    #[async_trait]
+    impl EventHandler for EchoAgent {
+        async fn handle_event(&self, event: AgentEvent) -> Result<Option<AgentEvent>> {
+            Ok(Some(AgentEvent::containment_complete(
+                event.correlation_id,
+                "pod".to_string(),
+                "default".to_string(),
+                false,
+            )))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_supervisor_republishes_handler_output() {
+        let bus = EventBus::new();
+        let supervisor = AgentSupervisor::new(bus.clone());
+        let agent = Arc::new(EchoAgent {
+            starts: AtomicUsize::new(0),
+        });
+
+        let mut observer = bus
+            .subscribe(
+                AgentType::Controller,
+                vec![AgentEventType::ContainmentComplete],
+            )
+            .await;
+
+        supervisor.spawn_agent(agent.clone()).await;
+
+        while bus.subscriber_count() < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        let correlation_id = Uuid::new_v4();
+        bus.publish(AgentEvent::fault_detected(
+            correlation_id,
+            FaultCluster::new("default".to_string()),
+        ))
+        .await
+        .unwrap();
+
+        let response = loop {
+            let event = observer.recv().await.unwrap();
+            if event.event_type == AgentEventType::ContainmentComplete {
+                break event;
+            }
+        };
+
+        assert_eq!(response.correlation_id, correlation_id);
+        assert_eq!(agent.starts.load(Ordering::SeqCst), 1);
+        assert_eq!(supervisor.running_loops().await, 1);
+
+        supervisor.shutdown().await;
+    }
+}
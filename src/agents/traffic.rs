@@ -0,0 +1,341 @@
+use chrono::Utc;
+use k8s_openapi::api::core::v1::Pod;
+use kube::api::{
+    ApiResource, DeleteParams, DynamicObject, GroupVersionKind, ListParams, Patch, PatchParams,
+    PostParams,
+};
+use kube::{Api, Client as KubeClient, ResourceExt};
+use std::collections::BTreeMap;
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use crate::crd::{TrafficRouting, CORRELATION_ID_LABEL, ISOLATED_LABEL, REDIRECT_LABEL_PREFIX};
+use crate::error::{RecistError, Result};
+use crate::models::{AcceptingNeighbor, TrafficRedirect};
+
+const ISTIO_GROUP: &str = "networking.istio.io";
+const ISTIO_VERSION: &str = "v1beta1";
+const TOTAL_WEIGHT: u32 = 100;
+const MANAGED_BY_LABEL: &str = "app.kubernetes.io/managed-by";
+const MANAGED_BY: &str = "recist";
+
+pub struct TrafficRouter {
+    kube_client: KubeClient,
+    routing: TrafficRouting,
+    cluster_domain: String,
+}
+
+impl TrafficRouter {
+    pub fn new(kube_client: KubeClient, routing: TrafficRouting, cluster_domain: String) -> Self {
+        Self {
+            kube_client,
+            routing,
+            cluster_domain,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.routing != TrafficRouting::None
+    }
+
+    pub async fn redirect(
+        &self,
+        namespace: &str,
+        service: &str,
+        from_pod: &str,
+        isolation_id: &str,
+        correlation_id: Uuid,
+        neighbors: &[AcceptingNeighbor],
+    ) -> Result<TrafficRedirect> {
+        let owners = self.existing_routes(namespace, service).await?;
+        if !owners.is_empty() {
+            return Err(RecistError::ActionRefused(format!(
+                "Service {}/{} is already routed by {}",
+                namespace,
+                service,
+                owners.join(", ")
+            )));
+        }
+
+        let weights = route_weights(neighbors);
+        let subset_label = redirect_label(isolation_id);
+        let host = self.service_host(namespace, service);
+        let name = format!("recist-redirect-{}-{}", service, &isolation_id[..8]);
+
+        let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
+        let mut subsets = Vec::new();
+        let mut routes = Vec::new();
+        for (index, (pod_name, weight)) in weights.iter().enumerate() {
+            let patch = serde_json::json!({
+                "metadata": {
+                    "labels": {
+                        subset_label.as_str(): index.to_string()
+                    }
+                }
+            });
+            pods.patch(pod_name, &PatchParams::default(), &Patch::Merge(&patch))
+                .await?;
+
+            let subset = format!("neighbor-{}", index);
+            subsets.push(serde_json::json!({
+                "name": subset,
+                "labels": { subset_label.as_str(): index.to_string() }
+            }));
+            routes.push(serde_json::json!({
+                "destination": { "host": host, "subset": subset },
+                "weight": weight
+            }));
+        }
+
+        let labels = redirect_labels(isolation_id, correlation_id);
+
+        let mut destination_rule = DynamicObject::new(&name, &destination_rule_resource())
+            .within(namespace)
+            .data(serde_json::json!({
+                "spec": { "host": host, "subsets": subsets }
+            }));
+        destination_rule.metadata.labels = Some(labels.clone());
+
+        let mut virtual_service = DynamicObject::new(&name, &virtual_service_resource())
+            .within(namespace)
+            .data(serde_json::json!({
+                "spec": { "hosts": [host], "http": [{ "route": routes }] }
+            }));
+        virtual_service.metadata.labels = Some(labels);
+
+        self.api(namespace, &destination_rule_resource())
+            .create(&PostParams::default(), &destination_rule)
+            .await?;
+        self.api(namespace, &virtual_service_resource())
+            .create(&PostParams::default(), &virtual_service)
+            .await?;
+
+        info!(
+            "Redirected traffic for Service {}/{} away from pod {}: {:?}",
+            namespace, service, from_pod, weights
+        );
+
+        Ok(TrafficRedirect {
+            from_pod: from_pod.to_string(),
+            to_pods: weights.keys().cloned().collect(),
+            namespace: namespace.to_string(),
+            service: service.to_string(),
+            weights,
+            created_at: Utc::now(),
+        })
+    }
+
+    pub async fn revert(&self, namespace: &str, isolation_id: &str) -> Result<()> {
+        let params = ListParams::default().labels(&format!("{}={}", ISOLATED_LABEL, isolation_id));
+
+        for resource in [virtual_service_resource(), destination_rule_resource()] {
+            let api = self.api(namespace, &resource);
+            match api
+                .delete_collection(&DeleteParams::default(), &params)
+                .await
+            {
+                Ok(_) => debug!(
+                    "Removed {} objects for isolation {} in {}",
+                    resource.kind, isolation_id, namespace
+                ),
+                Err(kube::Error::Api(ae)) if ae.code == 404 => {
+                    debug!("{} is not served, nothing to revert", resource.kind);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        let subset_label = redirect_label(isolation_id);
+        let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
+        let patch = serde_json::json!({
+            "metadata": {
+                "labels": {
+                    subset_label.as_str(): serde_json::Value::Null
+                }
+            }
+        });
+        for pod in pods
+            .list(&ListParams::default().labels(&subset_label))
+            .await?
+            .items
+        {
+            pods.patch(
+                &pod.name_any(),
+                &PatchParams::default(),
+                &Patch::Merge(&patch),
+            )
+            .await?;
+        }
+
+        info!(
+            "Reverted traffic redirects for isolation {} in {}",
+            isolation_id, namespace
+        );
+
+        Ok(())
+    }
+
+    async fn existing_routes(&self, namespace: &str, service: &str) -> Result<Vec<String>> {
+        let fqdn = self.service_host(namespace, service);
+        let mut owners = Vec::new();
+
+        for resource in [virtual_service_resource(), destination_rule_resource()] {
+            let objects = match self
+                .api(namespace, &resource)
+                .list(&ListParams::default())
+                .await
+            {
+                Ok(objects) => objects.items,
+                Err(kube::Error::Api(ae)) if ae.code == 404 => {
+                    debug!("{} is not served in {}", resource.kind, namespace);
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+            for object in objects {
+                let managed =
+                    object.labels().get(MANAGED_BY_LABEL).map(String::as_str) == Some(MANAGED_BY);
+                let spec = &object.data["spec"];
+                let hosts: Vec<&str> = spec["hosts"]
+                    .as_array()
+                    .map(|hosts| hosts.iter().filter_map(|h| h.as_str()).collect())
+                    .unwrap_or_default();
+                let routes_service = spec["host"]
+                    .as_str()
+                    .into_iter()
+                    .chain(hosts)
+                    .any(|host| names_service(host, &fqdn));
+
+                if routes_service && !managed {
+                    owners.push(format!("{} {}", resource.kind, object.name_any()));
+                }
+            }
+        }
+
+        Ok(owners)
+    }
+
+    fn service_host(&self, namespace: &str, service: &str) -> String {
+        format!("{}.{}.svc.{}", service, namespace, self.cluster_domain)
+    }
+
+    fn api(&self, namespace: &str, resource: &ApiResource) -> Api<DynamicObject> {
+        Api::namespaced_with(self.kube_client.clone(), namespace, resource)
+    }
+}
+
+pub fn route_weights(neighbors: &[AcceptingNeighbor]) -> BTreeMap<String, u32> {
+    if neighbors.is_empty() {
+        return BTreeMap::new();
+    }
+
+    let total: f64 = neighbors.iter().map(|n| n.accepted_load_fraction).sum();
+    let shares: Vec<f64> = neighbors
+        .iter()
+        .map(|n| {
+            if total > 0.0 {
+                n.accepted_load_fraction / total * TOTAL_WEIGHT as f64
+            } else {
+                TOTAL_WEIGHT as f64 / neighbors.len() as f64
+            }
+        })
+        .collect();
+
+    let mut weights: Vec<u32> = shares.iter().map(|share| share.floor() as u32).collect();
+    let mut remainders: Vec<usize> = (0..shares.len()).collect();
+    remainders.sort_by(|a, b| {
+        let ra = shares[*a] - shares[*a].floor();
+        let rb = shares[*b] - shares[*b].floor();
+        rb.total_cmp(&ra)
+    });
+
+    let assigned: u32 = weights.iter().sum();
+    for index in remainders
+        .into_iter()
+        .take((TOTAL_WEIGHT - assigned) as usize)
+    {
+        weights[index] += 1;
+    }
+
+    neighbors
+        .iter()
+        .zip(weights)
+        .map(|(neighbor, weight)| (neighbor.pod_name.clone(), weight))
+        .collect()
+}
+
+fn names_service(host: &str, fqdn: &str) -> bool {
+    host == fqdn || fqdn.starts_with(&format!("{}.", host))
+}
+
+fn redirect_label(isolation_id: &str) -> String {
+    format!("{}{}", REDIRECT_LABEL_PREFIX, isolation_id)
+}
+
+fn redirect_labels(isolation_id: &str, correlation_id: Uuid) -> BTreeMap<String, String> {
+    let mut labels = BTreeMap::new();
+    labels.insert(MANAGED_BY_LABEL.to_string(), MANAGED_BY.to_string());
+    labels.insert(ISOLATED_LABEL.to_string(), isolation_id.to_string());
+    labels.insert(CORRELATION_ID_LABEL.to_string(), correlation_id.to_string());
+    labels
+}
+
+fn virtual_service_resource() -> ApiResource {
+    ApiResource::from_gvk(&GroupVersionKind::gvk(
+        ISTIO_GROUP,
+        ISTIO_VERSION,
+        "VirtualService",
+    ))
+}
+
+fn destination_rule_resource() -> ApiResource {
+    ApiResource::from_gvk(&GroupVersionKind::gvk(
+        ISTIO_GROUP,
+        ISTIO_VERSION,
+        "DestinationRule",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn neighbor(pod_name: &str, accepted_load_fraction: f64) -> AcceptingNeighbor {
+        AcceptingNeighbor {
+            pod_name: pod_name.to_string(),
+            available_capacity: 0.9,
+            accepted_load_fraction,
+        }
+    }
+
+    #[test]
+    fn test_route_weights_sum_to_total() {
+        let weights =
+            route_weights(&[neighbor("a", 0.5), neighbor("b", 0.25), neighbor("c", 0.25)]);
+        assert_eq!(weights["a"], 50);
+        assert_eq!(weights["b"], 25);
+        assert_eq!(weights["c"], 25);
+
+        let weights = route_weights(&[neighbor("a", 0.1), neighbor("b", 0.1), neighbor("c", 0.1)]);
+        assert_eq!(weights.values().sum::<u32>(), TOTAL_WEIGHT);
+
+        let weights = route_weights(&[neighbor("a", 0.0), neighbor("b", 0.0)]);
+        assert_eq!(weights["a"], 50);
+        assert_eq!(weights["b"], 50);
+
+        assert!(route_weights(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_names_service() {
+        let fqdn = "web.default.svc.cluster.local";
+        assert!(names_service("web", fqdn));
+        assert!(names_service("web.default", fqdn));
+        assert!(names_service("web.default.svc", fqdn));
+        assert!(names_service(fqdn, fqdn));
+        assert!(!names_service("we", fqdn));
+        assert!(!names_service("web.other", fqdn));
+        assert!(!names_service("api.default.svc.cluster.local", fqdn));
+    }
+}
@@ -0,0 +1,87 @@
+use k8s_openapi::api::apps::v1::ReplicaSet;
+use k8s_openapi::api::batch::v1::Job;
+use k8s_openapi::api::core::v1::Pod;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
+use kube::api::{ApiResource, DynamicObject, GroupVersionKind};
+use kube::{Api, Client as KubeClient, ResourceExt};
+use tracing::debug;
+
+use crate::error::Result;
+use crate::models::WorkloadRef;
+
+pub struct WorkloadResolver {
+    kube_client: KubeClient,
+}
+
+impl WorkloadResolver {
+    pub fn new(kube_client: KubeClient) -> Self {
+        Self { kube_client }
+    }
+
+    pub async fn resolve(&self, namespace: &str, pod_name: &str) -> Result<Option<WorkloadRef>> {
+        let pods: Api<Pod> = Api::namespaced(self.kube_client.clone(), namespace);
+        let pod = pods.get(pod_name).await?;
+        self.resolve_pod(namespace, &pod).await
+    }
+
+    pub async fn resolve_pod(&self, namespace: &str, pod: &Pod) -> Result<Option<WorkloadRef>> {
+        let owner = match controller_owner(pod) {
+            Some(owner) => owner,
+            None => return Ok(None),
+        };
+
+        let parent = match owner.kind.as_str() {
+            "ReplicaSet" => {
+                let replica_sets: Api<ReplicaSet> =
+                    Api::namespaced(self.kube_client.clone(), namespace);
+                replica_sets
+                    .get_opt(&owner.name)
+                    .await?
+                    .as_ref()
+                    .and_then(controller_owner)
+            }
+            "Job" => {
+                let jobs: Api<Job> = Api::namespaced(self.kube_client.clone(), namespace);
+                jobs.get_opt(&owner.name)
+                    .await?
+                    .as_ref()
+                    .and_then(controller_owner)
+            }
+            _ => None,
+        };
+
+        let workload = WorkloadRef::from(parent.unwrap_or(owner));
+        debug!(
+            "Pod {}/{} is owned by {}",
+            namespace,
+            pod.name_any(),
+            workload
+        );
+
+        Ok(Some(workload))
+    }
+
+    pub fn api(&self, namespace: &str, workload: &WorkloadRef) -> Api<DynamicObject> {
+        Api::namespaced_with(
+            self.kube_client.clone(),
+            namespace,
+            &workload_resource(workload),
+        )
+    }
+}
+
+pub fn workload_resource(workload: &WorkloadRef) -> ApiResource {
+    let (group, version) = match workload.api_version.rsplit_once('/') {
+        Some((group, version)) => (group, version),
+        None => ("", workload.api_version.as_str()),
+    };
+    ApiResource::from_gvk(&GroupVersionKind::gvk(group, version, &workload.kind))
+}
+
+pub fn controller_owner<K: ResourceExt>(resource: &K) -> Option<OwnerReference> {
+    resource
+        .owner_references()
+        .iter()
+        .find(|owner| owner.controller == Some(true))
+        .cloned()
+}
@@ -9,6 +9,7 @@ use crate::error::{RecistError, Result};
 use crate::models::{LlmDiagnosisResponse, StrategyEvaluation, StrategyType};
 
 const CLAUDE_API_URL: &str = "https://api.anthropic.com/v1/messages";
+const CLAUDE_MODELS_URL: &str = "https://api.anthropic.com/v1/models";
 const ANTHROPIC_VERSION: &str = "2023-06-01";
 
 pub struct ClaudeClient {
@@ -129,6 +130,19 @@ impl LlmClient for ClaudeClient {
         ))
     }
 
+    async fn health_check(&self) -> Result<bool> {
+        let response = self
+            .client
+            .get(format!("{}/{}", CLAUDE_MODELS_URL, self.model))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .send()
+            .await
+            .map_err(|e| RecistError::LlmError(format!("Request failed: {}", e)))?;
+
+        Ok(response.status().is_success())
+    }
+
     fn provider_name(&self) -> &str {
         "Claude"
     }
@@ -153,6 +153,20 @@ impl LlmClient for GeminiClient {
         Ok(result.embedding.values)
     }
 
+    async fn health_check(&self) -> Result<bool> {
+        let response = self
+            .client
+            .get(format!(
+                "{}/{}?key={}",
+                GEMINI_API_URL, self.model, self.api_key
+            ))
+            .send()
+            .await
+            .map_err(|e| RecistError::LlmError(format!("Request failed: {}", e)))?;
+
+        Ok(response.status().is_success())
+    }
+
     fn provider_name(&self) -> &str {
         "Gemini"
     }
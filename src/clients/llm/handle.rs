@@ -0,0 +1,43 @@
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use super::traits::LlmClient;
+use crate::error::{RecistError, Result};
+
+#[derive(Clone, Default)]
+pub struct LlmClientHandle {
+    inner: Arc<RwLock<Option<Arc<dyn LlmClient>>>>,
+}
+
+impl LlmClientHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_client(client: Arc<dyn LlmClient>) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(Some(client))),
+        }
+    }
+
+    pub async fn set(&self, client: Arc<dyn LlmClient>) {
+        let mut inner = self.inner.write().await;
+        *inner = Some(client);
+    }
+
+    pub async fn clear(&self) {
+        *self.inner.write().await = None;
+    }
+
+    pub async fn get(&self) -> Result<Arc<dyn LlmClient>> {
+        self.inner
+            .read()
+            .await
+            .clone()
+            .ok_or_else(|| RecistError::LlmError("No LLM client configured".to_string()))
+    }
+
+    pub async fn is_configured(&self) -> bool {
+        self.inner.read().await.is_some()
+    }
+}
@@ -1,11 +1,13 @@
 mod claude;
 mod gemini;
+mod handle;
 mod ollama;
 mod openai;
 mod traits;
 
 pub use claude::ClaudeClient;
 pub use gemini::GeminiClient;
+pub use handle::LlmClientHandle;
 pub use ollama::OllamaClient;
 pub use openai::OpenAIClient;
 pub use traits::*;
@@ -133,6 +133,17 @@ impl LlmClient for OllamaClient {
         Ok(result.embedding)
     }
 
+    async fn health_check(&self) -> Result<bool> {
+        let response = self
+            .client
+            .get(format!("{}/api/tags", self.base_url))
+            .send()
+            .await
+            .map_err(|e| RecistError::LlmError(format!("Request failed: {}", e)))?;
+
+        Ok(response.status().is_success())
+    }
+
     fn provider_name(&self) -> &str {
         "Ollama"
     }
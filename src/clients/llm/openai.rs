@@ -170,6 +170,18 @@ impl LlmClient for OpenAIClient {
             .unwrap_or_default())
     }
 
+    async fn health_check(&self) -> Result<bool> {
+        let response = self
+            .client
+            .get(format!("{}/models/{}", self.base_url, self.model))
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .send()
+            .await
+            .map_err(|e| RecistError::LlmError(format!("Request failed: {}", e)))?;
+
+        Ok(response.status().is_success())
+    }
+
     fn provider_name(&self) -> &str {
         "OpenAI"
     }
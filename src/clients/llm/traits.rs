@@ -19,6 +19,8 @@ pub trait LlmClient: Send + Sync {
 
     async fn generate_embedding(&self, text: &str) -> Result<Vec<f32>>;
 
+    async fn health_check(&self) -> Result<bool>;
+
     fn provider_name(&self) -> &str;
 
     fn model_name(&self) -> &str;
@@ -32,6 +34,8 @@ pub struct DiagnosisRequest {
     pub pod_name: String,
     pub namespace: String,
     pub error_type: String,
+    #[serde(default)]
+    pub related_faults: Vec<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -117,6 +121,14 @@ pub fn build_diagnosis_prompt(request: &DiagnosisRequest) -> String {
     }
     prompt.push('\n');
 
+    if !request.related_faults.is_empty() {
+        prompt.push_str("=== CORRELATED FAULTS ===\n");
+        for fault in &request.related_faults {
+            prompt.push_str(&format!("- {}\n", fault));
+        }
+        prompt.push('\n');
+    }
+
     prompt.push_str("Based on the above information, provide your diagnosis in JSON format.");
 
     prompt
@@ -2,11 +2,15 @@ use chrono::{DateTime, Utc};
 use prometheus_http_query::{Client, InstantVector, RangeVector};
 use std::collections::HashMap;
 use std::time::Duration;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use crate::config::PrometheusConfig;
 use crate::error::{RecistError, Result};
 
+type SignalSetter = fn(&mut PodMetrics, f64);
+
+pub type PodMetricHistory = HashMap<String, HashMap<&'static str, Vec<(DateTime<Utc>, f64)>>>;
+
 pub struct PrometheusClient {
     client: Client,
     timeout: Duration,
@@ -113,31 +117,50 @@ impl PrometheusClient {
         Ok(series)
     }
 
-    pub async fn get_pod_cpu_usage(&self, namespace: &str, pod: &str) -> Result<f64> {
-        let query = format!(
+    pub fn cpu_usage_query(namespace: &str, pod: &str) -> String {
+        format!(
             r#"sum(rate(container_cpu_usage_seconds_total{{namespace="{}", pod="{}"}}[5m])) by (pod)"#,
             namespace, pod
-        );
+        )
+    }
+
+    pub fn memory_usage_query(namespace: &str, pod: &str) -> String {
+        format!(
+            r#"sum(container_memory_usage_bytes{{namespace="{}", pod="{}"}}) by (pod) / sum(container_spec_memory_limit_bytes{{namespace="{}", pod="{}"}}) by (pod)"#,
+            namespace, pod, namespace, pod
+        )
+    }
+
+    pub fn error_rate_query(namespace: &str, pod: &str) -> String {
+        format!(
+            r#"sum(rate(http_requests_total{{namespace="{}", pod="{}", status=~"5.."}}[5m])) / sum(rate(http_requests_total{{namespace="{}", pod="{}"}}[5m]))"#,
+            namespace, pod, namespace, pod
+        )
+    }
+
+    pub fn latency_p99_query(namespace: &str, pod: &str) -> String {
+        format!(
+            r#"histogram_quantile(0.99, sum(rate(http_request_duration_seconds_bucket{{namespace="{}", pod="{}"}}[5m])) by (le)) * 1000"#,
+            namespace, pod
+        )
+    }
+
+    pub async fn get_pod_cpu_usage(&self, namespace: &str, pod: &str) -> Result<f64> {
+        let query = Self::cpu_usage_query(namespace, pod);
 
         let samples = self.query_instant(&query).await?;
         Ok(samples.first().map(|s| s.value).unwrap_or(0.0))
     }
 
     pub async fn get_pod_memory_usage(&self, namespace: &str, pod: &str) -> Result<f64> {
-        let query = format!(
-            r#"sum(container_memory_usage_bytes{{namespace="{}", pod="{}"}}) by (pod) / sum(container_spec_memory_limit_bytes{{namespace="{}", pod="{}"}}) by (pod)"#,
-            namespace, pod, namespace, pod
-        );
+        let query = Self::memory_usage_query(namespace, pod);
 
         let samples = self.query_instant(&query).await?;
         Ok(samples.first().map(|s| s.value).unwrap_or(0.0))
     }
 
     pub async fn get_pod_error_rate(&self, namespace: &str, pod: &str) -> Result<f64> {
-        let query = format!(
-            r#"sum(rate(http_requests_total{{namespace="{}", pod="{}", status=~"5.."}}[5m])) / sum(rate(http_requests_total{{namespace="{}", pod="{}"}}[5m]))"#,
-            namespace, pod, namespace, pod
-        );
+        let query = Self::error_rate_query(namespace, pod);
 
         let samples = self.query_instant(&query).await?;
         let value = samples.first().map(|s| s.value).unwrap_or(0.0);
@@ -145,58 +168,136 @@ impl PrometheusClient {
     }
 
     pub async fn get_pod_latency_p99(&self, namespace: &str, pod: &str) -> Result<f64> {
-        let query = format!(
-            r#"histogram_quantile(0.99, sum(rate(http_request_duration_seconds_bucket{{namespace="{}", pod="{}"}}[5m])) by (le))"#,
-            namespace, pod
-        );
+        let query = Self::latency_p99_query(namespace, pod);
 
         let samples = self.query_instant(&query).await?;
-        let latency_seconds = samples.first().map(|s| s.value).unwrap_or(0.0);
-        Ok(latency_seconds * 1000.0)
+        Ok(samples.first().map(|s| s.value).unwrap_or(0.0))
     }
 
-    pub async fn get_all_pod_metrics(&self, namespace: &str) -> Result<Vec<PodMetrics>> {
-        let cpu_query = format!(
-            r#"sum(rate(container_cpu_usage_seconds_total{{namespace="{}"}}[5m])) by (pod)"#,
-            namespace
-        );
+    pub fn namespace_metric_queries(namespace: &str) -> [(&'static str, String); 4] {
+        [
+            (
+                "cpu",
+                format!(
+                    r#"sum(rate(container_cpu_usage_seconds_total{{namespace="{}"}}[5m])) by (pod)"#,
+                    namespace
+                ),
+            ),
+            (
+                "memory",
+                format!(
+                    r#"sum(container_memory_usage_bytes{{namespace="{}"}}) by (pod) / sum(container_spec_memory_limit_bytes{{namespace="{}"}}) by (pod)"#,
+                    namespace, namespace
+                ),
+            ),
+            (
+                "errorRate",
+                format!(
+                    r#"(sum(rate(http_requests_total{{namespace="{0}", status=~"5.."}}[5m])) by (pod) or sum(rate(http_requests_total{{namespace="{0}"}}[5m])) by (pod) * 0) / sum(rate(http_requests_total{{namespace="{0}"}}[5m])) by (pod)"#,
+                    namespace
+                ),
+            ),
+            (
+                "latency",
+                format!(
+                    r#"histogram_quantile(0.99, sum(rate(http_request_duration_seconds_bucket{{namespace="{}"}}[5m])) by (pod, le)) * 1000"#,
+                    namespace
+                ),
+            ),
+        ]
+    }
 
-        let memory_query = format!(
-            r#"sum(container_memory_usage_bytes{{namespace="{}"}}) by (pod) / sum(container_spec_memory_limit_bytes{{namespace="{}"}}) by (pod)"#,
-            namespace, namespace
-        );
+    pub async fn get_all_pod_metrics(&self, namespace: &str) -> Result<Vec<PodMetrics>> {
+        let [(_, cpu_query), (_, memory_query), (_, error_rate_query), (_, latency_query)] =
+            Self::namespace_metric_queries(namespace);
 
-        let cpu_samples = self.query_instant(&cpu_query).await?;
-        let memory_samples = self.query_instant(&memory_query).await?;
+        let (cpu_samples, memory_samples, error_rate_samples, latency_samples) = tokio::try_join!(
+            self.query_instant(&cpu_query),
+            self.query_instant(&memory_query),
+            self.query_instant(&error_rate_query),
+            self.query_instant(&latency_query),
+        )?;
 
         let mut metrics_map: HashMap<String, PodMetrics> = HashMap::new();
 
-        for sample in cpu_samples {
-            if let Some(pod_name) = sample.labels.get("pod") {
-                metrics_map
-                    .entry(pod_name.clone())
-                    .or_insert_with(|| PodMetrics {
-                        pod_name: pod_name.clone(),
-                        namespace: namespace.to_string(),
-                        cpu_usage: 0.0,
-                        memory_usage: 0.0,
-                        error_rate: 0.0,
-                        latency_ms: 0.0,
-                    })
-                    .cpu_usage = sample.value;
+        let signals: [(Vec<MetricSample>, SignalSetter); 4] = [
+            (cpu_samples, |m, v| m.cpu_usage = Some(v)),
+            (memory_samples, |m, v| m.memory_usage = Some(v)),
+            (error_rate_samples, |m, v| m.error_rate = Some(v)),
+            (latency_samples, |m, v| m.latency_ms = Some(v)),
+        ];
+
+        for (samples, apply) in signals {
+            for sample in samples.into_iter().filter(|s| s.value.is_finite()) {
+                if let Some(pod_name) = sample.labels.get("pod") {
+                    let metrics = metrics_map.entry(pod_name.clone()).or_insert_with(|| {
+                        PodMetrics::new(pod_name.clone(), namespace.to_string())
+                    });
+                    apply(metrics, sample.value);
+                }
             }
         }
 
-        for sample in memory_samples {
-            if let Some(pod_name) = sample.labels.get("pod") {
-                if let Some(metrics) = metrics_map.get_mut(pod_name) {
-                    let value = sample.value;
-                    metrics.memory_usage = if value.is_nan() { 0.0 } else { value };
+        debug!(
+            "Collected metrics for {} pods in namespace {}",
+            metrics_map.len(),
+            namespace
+        );
+
+        Ok(metrics_map.into_values().collect())
+    }
+
+    pub async fn get_pod_metric_history(
+        &self,
+        namespace: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        step_seconds: u64,
+    ) -> Result<PodMetricHistory> {
+        let queries = Self::namespace_metric_queries(namespace);
+        let results = futures::future::try_join_all(
+            queries
+                .iter()
+                .map(|(_, query)| self.query_range(query, start, end, step_seconds)),
+        )
+        .await?;
+
+        let mut history: PodMetricHistory = HashMap::new();
+        for ((name, _), series) in queries.iter().zip(results) {
+            for ts in series {
+                if let Some(pod_name) = ts.labels.get("pod") {
+                    let values: Vec<(DateTime<Utc>, f64)> = ts
+                        .values
+                        .into_iter()
+                        .filter(|(_, v)| v.is_finite())
+                        .collect();
+                    history
+                        .entry(pod_name.clone())
+                        .or_default()
+                        .insert(*name, values);
                 }
             }
         }
 
-        Ok(metrics_map.into_values().collect())
+        Ok(history)
+    }
+
+    pub async fn query_value(&self, query: &str) -> Result<Option<f64>> {
+        let samples = self.query_instant(query).await?;
+        Ok(samples
+            .iter()
+            .map(|s| s.value)
+            .find(|value| value.is_finite()))
+    }
+
+    pub async fn health_check(&self) -> Result<bool> {
+        match self.query_instant("vector(1)").await {
+            Ok(samples) => Ok(!samples.is_empty()),
+            Err(e) => {
+                warn!("Prometheus health check failed: {}", e);
+                Ok(false)
+            }
+        }
     }
 }
 
@@ -217,13 +318,24 @@ pub struct MetricTimeSeries {
 pub struct PodMetrics {
     pub pod_name: String,
     pub namespace: String,
-    pub cpu_usage: f64,
-    pub memory_usage: f64,
-    pub error_rate: f64,
-    pub latency_ms: f64,
+    pub cpu_usage: Option<f64>,
+    pub memory_usage: Option<f64>,
+    pub error_rate: Option<f64>,
+    pub latency_ms: Option<f64>,
 }
 
 impl PodMetrics {
+    pub fn new(pod_name: String, namespace: String) -> Self {
+        Self {
+            pod_name,
+            namespace,
+            cpu_usage: None,
+            memory_usage: None,
+            error_rate: None,
+            latency_ms: None,
+        }
+    }
+
     pub fn exceeds_threshold(
         &self,
         cpu_threshold: f64,
@@ -233,28 +345,45 @@ impl PodMetrics {
     ) -> Vec<String> {
         let mut violations = Vec::new();
 
-        if self.cpu_usage > cpu_threshold {
-            violations.push(format!("CPU usage {} > {}", self.cpu_usage, cpu_threshold));
+        if let Some(cpu) = self.cpu_usage.filter(|v| *v > cpu_threshold) {
+            violations.push(format!("CPU usage {} > {}", cpu, cpu_threshold));
         }
-        if self.memory_usage > memory_threshold {
-            violations.push(format!(
-                "Memory usage {} > {}",
-                self.memory_usage, memory_threshold
-            ));
+        if let Some(memory) = self.memory_usage.filter(|v| *v > memory_threshold) {
+            violations.push(format!("Memory usage {} > {}", memory, memory_threshold));
         }
-        if self.error_rate > error_rate_threshold {
+        if let Some(error_rate) = self.error_rate.filter(|v| *v > error_rate_threshold) {
             violations.push(format!(
                 "Error rate {} > {}",
-                self.error_rate, error_rate_threshold
+                error_rate, error_rate_threshold
             ));
         }
-        if self.latency_ms > latency_threshold {
-            violations.push(format!(
-                "Latency {}ms > {}ms",
-                self.latency_ms, latency_threshold
-            ));
+        if let Some(latency) = self.latency_ms.filter(|v| *v > latency_threshold) {
+            violations.push(format!("Latency {}ms > {}ms", latency, latency_threshold));
         }
 
         violations
     }
+
+    pub fn signal(&self, name: &str) -> Option<f64> {
+        match name {
+            "cpu" => self.cpu_usage,
+            "memory" => self.memory_usage,
+            "errorRate" => self.error_rate,
+            "latency" => self.latency_ms,
+            _ => None,
+        }
+    }
+
+    pub fn unknown_signals(&self) -> Vec<&'static str> {
+        [
+            ("cpu", self.cpu_usage),
+            ("memory", self.memory_usage),
+            ("errorRate", self.error_rate),
+            ("latency", self.latency_ms),
+        ]
+        .into_iter()
+        .filter(|(_, value)| value.is_none())
+        .map(|(name, _)| name)
+        .collect()
+    }
 }
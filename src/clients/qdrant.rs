@@ -266,6 +266,16 @@ impl QdrantClient {
                 .unwrap_or(0),
         })
     }
+
+    pub async fn health_check(&self) -> Result<bool> {
+        match self.client.health_check().await {
+            Ok(_) => Ok(true),
+            Err(e) => {
+                warn!("Qdrant health check failed: {}", e);
+                Ok(false)
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
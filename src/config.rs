@@ -6,6 +6,9 @@ pub struct AppConfig {
     #[serde(default = "default_namespace")]
     pub namespace: String,
 
+    #[serde(default = "default_cluster_domain")]
+    pub cluster_domain: String,
+
     pub prometheus: PrometheusConfig,
     pub loki: LokiConfig,
     pub qdrant: QdrantConfig,
@@ -22,6 +25,10 @@ fn default_namespace() -> String {
     "recist-system".to_string()
 }
 
+fn default_cluster_domain() -> String {
+    "cluster.local".to_string()
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct PrometheusConfig {
     pub url: String,
@@ -107,6 +114,8 @@ impl AppConfig {
 
         Ok(Self {
             namespace: std::env::var("NAMESPACE").unwrap_or_else(|_| default_namespace()),
+            cluster_domain: std::env::var("CLUSTER_DOMAIN")
+                .unwrap_or_else(|_| default_cluster_domain()),
             prometheus: PrometheusConfig {
                 url: prometheus_url,
                 timeout_seconds: std::env::var("PROMETHEUS_TIMEOUT")
@@ -0,0 +1,154 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::agents::{Agent, EventHandler};
+use crate::error::Result;
+use crate::models::{
+    AgentEvent, AgentEventType, AgentType, DiagnosisHypothesis, EventPayload,
+    HealingCompletePayload,
+};
+
+const PROGRESS_RETENTION_MINUTES: i64 = 60;
+
+#[derive(Clone, Debug)]
+pub struct HealingProgress {
+    pub contained_pods: HashMap<String, DateTime<Utc>>,
+    pub refusals: HashMap<String, String>,
+    pub downgrades: HashMap<String, String>,
+    pub diagnosis: Option<DiagnosisHypothesis>,
+    pub diagnosed_at: Option<DateTime<Utc>>,
+    pub healing: Option<HealingCompletePayload>,
+    pub healed_at: Option<DateTime<Utc>>,
+    pub knowledge_entry_id: Option<Uuid>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl HealingProgress {
+    fn new() -> Self {
+        Self {
+            contained_pods: HashMap::new(),
+            refusals: HashMap::new(),
+            downgrades: HashMap::new(),
+            diagnosis: None,
+            diagnosed_at: None,
+            healing: None,
+            healed_at: None,
+            knowledge_entry_id: None,
+            updated_at: Utc::now(),
+        }
+    }
+
+    pub fn contained_at(&self, pod_name: &str) -> Option<DateTime<Utc>> {
+        self.contained_pods.get(pod_name).copied()
+    }
+
+    pub fn refusal(&self, pod_name: &str) -> Option<&str> {
+        self.healing
+            .as_ref()
+            .filter(|healing| healing.context.pod_name == pod_name)
+            .and_then(|healing| healing.refusal_reason.as_deref())
+            .or_else(|| self.refusals.get(pod_name).map(String::as_str))
+    }
+
+    pub fn downgrade(&self, pod_name: &str) -> Option<&str> {
+        self.downgrades.get(pod_name).map(String::as_str)
+    }
+}
+
+#[derive(Default)]
+pub struct HealingTracker {
+    progress: RwLock<HashMap<Uuid, HealingProgress>>,
+}
+
+impl HealingTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn progress(&self, correlation_id: &Uuid) -> Option<HealingProgress> {
+        self.progress.read().await.get(correlation_id).cloned()
+    }
+
+    pub async fn record(&self, event: &AgentEvent) {
+        let mut progress = self.progress.write().await;
+
+        let cutoff = Utc::now() - ChronoDuration::minutes(PROGRESS_RETENTION_MINUTES);
+        progress.retain(|_, p| p.updated_at > cutoff);
+
+        let entry = progress
+            .entry(event.correlation_id)
+            .or_insert_with(HealingProgress::new);
+
+        match &event.payload {
+            EventPayload::ContainmentComplete(payload) => {
+                entry
+                    .contained_pods
+                    .insert(payload.pod_name.clone(), event.timestamp);
+                if let Some(reason) = &payload.refusal_reason {
+                    entry
+                        .refusals
+                        .insert(payload.pod_name.clone(), reason.clone());
+                }
+                if let Some(reason) = &payload.downgrade_reason {
+                    entry
+                        .downgrades
+                        .insert(payload.pod_name.clone(), reason.clone());
+                }
+            }
+            EventPayload::DiagnosisComplete(payload) => {
+                entry.diagnosis = Some(payload.hypothesis.clone());
+                entry.diagnosed_at = Some(event.timestamp);
+            }
+            EventPayload::HealingComplete(payload) => {
+                entry.healing = Some(payload.clone());
+                entry.healed_at = Some(event.timestamp);
+            }
+            EventPayload::KnowledgeUpdated(payload) => {
+                entry.knowledge_entry_id = Some(payload.entry.id);
+            }
+            _ => return,
+        }
+        entry.updated_at = Utc::now();
+
+        debug!(
+            "Recorded {:?} for correlation {}",
+            event.event_type, event.correlation_id
+        );
+    }
+}
+
+#[async_trait]
+impl Agent for HealingTracker {
+    fn agent_type(&self) -> AgentType {
+        AgentType::Controller
+    }
+
+    async fn start(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn stop(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn subscribe_to(&self) -> Vec<AgentEventType> {
+        vec![
+            AgentEventType::ContainmentComplete,
+            AgentEventType::DiagnosisComplete,
+            AgentEventType::HealingComplete,
+            AgentEventType::KnowledgeUpdated,
+        ]
+    }
+}
+
+#[async_trait]
+impl EventHandler for HealingTracker {
+    async fn handle_event(&self, event: AgentEvent) -> Result<Option<AgentEvent>> {
+        self.record(&event).await;
+        Ok(None)
+    }
+}
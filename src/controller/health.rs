@@ -0,0 +1,101 @@
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::collections::HashMap;
+use std::future::Future;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use crate::crd::PolicyCondition;
+use crate::error::Result;
+
+const HEALTH_CHECK_TTL_SECONDS: i64 = 60;
+
+#[derive(Clone, Debug)]
+pub struct DependencyHealth {
+    pub healthy: bool,
+    pub message: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+impl DependencyHealth {
+    pub fn healthy() -> Self {
+        Self {
+            healthy: true,
+            message: None,
+            checked_at: Utc::now(),
+        }
+    }
+
+    pub fn unhealthy(message: String) -> Self {
+        Self {
+            healthy: false,
+            message: Some(message),
+            checked_at: Utc::now(),
+        }
+    }
+
+    pub fn to_condition(&self, condition_type: &str) -> PolicyCondition {
+        PolicyCondition {
+            condition_type: condition_type.to_string(),
+            status: if self.healthy { "True" } else { "False" }.to_string(),
+            last_transition_time: self.checked_at.to_rfc3339(),
+            reason: Some(
+                if self.healthy {
+                    "HealthCheckPassed"
+                } else {
+                    "HealthCheckFailed"
+                }
+                .to_string(),
+            ),
+            message: self.message.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct HealthCache {
+    entries: RwLock<HashMap<String, DependencyHealth>>,
+}
+
+impl HealthCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn check<F>(&self, key: &str, check: F) -> DependencyHealth
+    where
+        F: Future<Output = Result<bool>>,
+    {
+        if let Some(cached) = self.entries.read().await.get(key) {
+            if Utc::now() - cached.checked_at < ChronoDuration::seconds(HEALTH_CHECK_TTL_SECONDS) {
+                return cached.clone();
+            }
+        }
+
+        let health = match check.await {
+            Ok(true) => DependencyHealth::healthy(),
+            Ok(false) => DependencyHealth::unhealthy(format!("{} health check failed", key)),
+            Err(e) => DependencyHealth::unhealthy(e.to_string()),
+        };
+
+        if health.healthy {
+            debug!("Dependency {} is healthy", key);
+        } else {
+            warn!(
+                "Dependency {} is unhealthy: {}",
+                key,
+                health.message.as_deref().unwrap_or_default()
+            );
+        }
+
+        self.entries
+            .write()
+            .await
+            .insert(key.to_string(), health.clone());
+
+        health
+    }
+
+    pub async fn evict(&self, key: &str) {
+        self.entries.write().await.remove(key);
+    }
+}
@@ -0,0 +1,131 @@
+use k8s_openapi::api::core::v1::Secret;
+use kube::{Api, Client, ResourceExt};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, info};
+
+use crate::clients::llm::{create_llm_client, LlmClient};
+use crate::crd::{LlmConfig, LlmProvider, SelfHealingPolicy};
+use crate::error::{RecistError, Result};
+
+struct CachedLlmClient {
+    generation: i64,
+    secret_version: Option<String>,
+    client: Arc<dyn LlmClient>,
+}
+
+pub struct LlmClientCache {
+    client: Client,
+    entries: RwLock<HashMap<String, CachedLlmClient>>,
+}
+
+impl LlmClientCache {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            entries: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub async fn get_or_build(&self, policy: &SelfHealingPolicy) -> Result<Arc<dyn LlmClient>> {
+        let key = policy_key(policy);
+        let namespace = policy.namespace().unwrap_or_default();
+        let generation = policy.metadata.generation.unwrap_or(0);
+        let llm_config = &policy.spec.llm_config;
+
+        let (api_key, secret_version) = self.resolve_api_key(&namespace, llm_config).await?;
+
+        if let Some(cached) = self.entries.read().await.get(&key) {
+            if cached.generation == generation && cached.secret_version == secret_version {
+                debug!("Using cached LLM client for policy {}", key);
+                return Ok(cached.client.clone());
+            }
+        }
+
+        info!(
+            "Building {:?} LLM client with model {} for policy {} (generation {})",
+            llm_config.provider, llm_config.model, key, generation
+        );
+
+        let client: Arc<dyn LlmClient> = Arc::from(
+            create_llm_client(
+                &llm_config.provider,
+                &api_key,
+                &llm_config.model,
+                llm_config.base_url.as_deref(),
+                llm_config.timeout_seconds,
+            )
+            .await?,
+        );
+
+        self.entries.write().await.insert(
+            key,
+            CachedLlmClient {
+                generation,
+                secret_version,
+                client: client.clone(),
+            },
+        );
+
+        Ok(client)
+    }
+
+    pub async fn evict(&self, key: &str) {
+        if self.entries.write().await.remove(key).is_some() {
+            debug!("Evicted cached LLM client for policy {}", key);
+        }
+    }
+
+    async fn resolve_api_key(
+        &self,
+        namespace: &str,
+        config: &LlmConfig,
+    ) -> Result<(String, Option<String>)> {
+        if config.api_key_secret.is_empty() {
+            if config.provider == LlmProvider::Ollama {
+                return Ok((String::new(), None));
+            }
+            return Err(RecistError::ConfigError(format!(
+                "llmConfig.apiKeySecret is required for provider {:?}",
+                config.provider
+            )));
+        }
+
+        let secrets: Api<Secret> = Api::namespaced(self.client.clone(), namespace);
+        let secret = secrets
+            .get_opt(&config.api_key_secret)
+            .await?
+            .ok_or_else(|| {
+                RecistError::NotFound(format!("Secret {}/{}", namespace, config.api_key_secret))
+            })?;
+
+        let value = secret
+            .data
+            .as_ref()
+            .and_then(|data| data.get(&config.api_key_secret_key))
+            .ok_or_else(|| {
+                RecistError::ConfigError(format!(
+                    "Secret {}/{} has no key '{}'",
+                    namespace, config.api_key_secret, config.api_key_secret_key
+                ))
+            })?;
+
+        let api_key = String::from_utf8(value.0.clone()).map_err(|e| {
+            RecistError::ConfigError(format!(
+                "Secret {}/{} key '{}' is not valid UTF-8: {}",
+                namespace, config.api_key_secret, config.api_key_secret_key, e
+            ))
+        })?;
+
+        Ok((api_key.trim().to_string(), secret.resource_version()))
+    }
+}
+
+pub fn policy_key(policy: &SelfHealingPolicy) -> String {
+    format!(
+        "{}/{}",
+        policy.namespace().unwrap_or_default(),
+        policy.name_any()
+    )
+}
@@ -1,3 +1,11 @@
+mod healing_tracker;
+mod health;
+mod llm_cache;
+mod policy_agents;
 mod reconciler;
 
+pub use healing_tracker::*;
+pub use health::*;
+pub use llm_cache::*;
+pub use policy_agents::*;
 pub use reconciler::*;
@@ -0,0 +1,166 @@
+use kube::ResourceExt;
+use std::sync::Arc;
+use tracing::info;
+
+use super::{HealingTracker, ReconcilerContext};
+use crate::agents::{
+    AgentSupervisor, ContainmentAgent, DiagnosisAgent, KnowledgeAgent, MetaCognitiveAgent,
+};
+use crate::clients::llm::LlmClientHandle;
+use crate::crd::SelfHealingPolicy;
+use crate::error::Result;
+use crate::eventbus::EventBus;
+
+pub struct PolicyAgents {
+    pub policy_key: String,
+    pub generation: i64,
+    pub target_namespaces: Vec<String>,
+    pub event_bus: EventBus,
+    pub llm: LlmClientHandle,
+    pub containment_agent: Arc<ContainmentAgent>,
+    pub diagnosis_agent: Arc<DiagnosisAgent>,
+    pub metacognitive_agent: Arc<MetaCognitiveAgent>,
+    pub knowledge_agent: Arc<KnowledgeAgent>,
+    healing_tracker: Arc<HealingTracker>,
+    supervisor: AgentSupervisor,
+}
+
+impl PolicyAgents {
+    pub async fn new(ctx: &ReconcilerContext, policy: &SelfHealingPolicy) -> Result<Self> {
+        let policy_key = super::policy_key(policy);
+        let spec = &policy.spec;
+        let event_bus = EventBus::new();
+        let llm = LlmClientHandle::new();
+
+        let qdrant = ctx
+            .qdrant_for_dimensions(spec.knowledge_config.embedding_dimensions)
+            .await?;
+
+        let containment_agent = Arc::new(
+            ContainmentAgent::new(
+                ctx.prometheus.clone(),
+                event_bus.clone(),
+                spec,
+                policy_key.clone(),
+                ctx.config.cluster_domain.clone(),
+            )
+            .await?,
+        );
+
+        let diagnosis_agent = Arc::new(
+            DiagnosisAgent::new(
+                ctx.prometheus.clone(),
+                ctx.loki.clone(),
+                llm.clone(),
+                event_bus.clone(),
+                spec.diagnosis_config.clone(),
+            )
+            .await?,
+        );
+
+        let metacognitive_agent = Arc::new(
+            MetaCognitiveAgent::new(
+                llm.clone(),
+                event_bus.clone(),
+                spec.metacognitive_config.clone(),
+                spec.containment_config.forecasting.clone(),
+                policy_key.clone(),
+            )
+            .await?,
+        );
+
+        let knowledge_agent = Arc::new(
+            KnowledgeAgent::new(
+                qdrant,
+                ctx.redis.clone(),
+                llm.clone(),
+                event_bus.clone(),
+                spec.knowledge_config.clone(),
+                policy_key.clone(),
+            )
+            .await?,
+        );
+
+        let supervisor = AgentSupervisor::new(event_bus.clone());
+
+        Ok(Self {
+            policy_key,
+            generation: policy.metadata.generation.unwrap_or(0),
+            target_namespaces: target_namespaces(policy),
+            event_bus,
+            llm,
+            containment_agent,
+            diagnosis_agent,
+            metacognitive_agent,
+            knowledge_agent,
+            healing_tracker: ctx.healing_tracker.clone(),
+            supervisor,
+        })
+    }
+
+    pub async fn start(&self) {
+        info!(
+            "Starting agents for policy {} (generation {}) on namespaces {:?}",
+            self.policy_key, self.generation, self.target_namespaces
+        );
+
+        self.supervisor
+            .spawn_agent(self.containment_agent.clone())
+            .await;
+        self.supervisor
+            .spawn_agent(self.diagnosis_agent.clone())
+            .await;
+        self.supervisor
+            .spawn_agent(self.metacognitive_agent.clone())
+            .await;
+        self.supervisor
+            .spawn_agent(self.knowledge_agent.clone())
+            .await;
+        self.supervisor
+            .spawn_agent(self.healing_tracker.clone())
+            .await;
+    }
+
+    pub async fn resume_containment(&self) {
+        if self.supervisor.check_loop_running().await {
+            return;
+        }
+
+        self.supervisor
+            .spawn_check_loop(
+                self.containment_agent.clone(),
+                self.target_namespaces.clone(),
+            )
+            .await;
+    }
+
+    pub async fn pause_containment(&self) {
+        if self.supervisor.check_loop_running().await {
+            info!(
+                "Pausing containment for policy {} until an LLM client is available",
+                self.policy_key
+            );
+        }
+        self.supervisor.stop_check_loop().await;
+    }
+
+    pub async fn shutdown(&self) {
+        info!(
+            "Stopping agents for policy {} (generation {})",
+            self.policy_key, self.generation
+        );
+        self.supervisor.shutdown().await;
+    }
+
+    pub async fn running_loops(&self) -> usize {
+        self.supervisor.running_loops().await
+    }
+}
+
+pub fn target_namespaces(policy: &SelfHealingPolicy) -> Vec<String> {
+    if policy.spec.target_namespaces.is_empty() {
+        vec![policy.namespace().unwrap_or_default()]
+    } else {
+        policy.spec.target_namespaces.clone()
+    }
+}
@@ -1,40 +1,60 @@
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use futures::StreamExt;
+use k8s_openapi::api::core::v1::Secret;
 use kube::{
     api::{Api, ListParams, Patch, PatchParams},
     client::Client,
     runtime::{
-        controller::{Action, Controller},
-        watcher::Config as WatcherConfig,
+        controller::{Action, Config as ControllerConfig, Controller},
+        reflector::ObjectRef,
+        watcher::{metadata_watcher, watcher, Config as WatcherConfig, Event as WatcherEvent},
+        WatchStreamExt,
     },
     Resource, ResourceExt,
 };
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeSet, HashMap};
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
+use uuid::Uuid;
 
-use crate::agents::{
-    ContainmentAgent, DiagnosisAgent, EventHandler, KnowledgeAgent, MetaCognitiveAgent,
+use super::{
+    policy_key, DependencyHealth, HealingProgress, HealingTracker, HealthCache, LlmClientCache,
+    PolicyAgents,
 };
-use crate::clients::llm::{create_llm_client, LlmClient};
 use crate::clients::{LokiClient, PrometheusClient, QdrantClient, RedisClient};
 use crate::config::AppConfig;
 use crate::crd::{
-    HealingEvent, HealingEventSpec, HealingEventStatus, HealingPhase, PolicyCondition,
-    SelfHealingPolicy, SelfHealingPolicyStatus, TriggerReason,
+    ActionResult, AppliedAction, HealingEvent, HealingEventCondition, HealingEventSpec,
+    HealingEventStatus, HealingOutcome, HealingPhase, PhaseTiming, PhaseTransition,
+    PolicyCondition, SelfHealingPolicy, SelfHealingPolicyStatus, CORRELATION_ID_LABEL,
+    POLICY_NAMESPACE_LABEL, POLICY_NAME_LABEL,
 };
 use crate::error::{RecistError, Result};
-use crate::eventbus::EventBus;
-use crate::models::{AgentEvent, AgentEventType, HealingContext};
+use crate::models::{
+    AgentEvent, AgentEventType, HealingCompletePayload, HealingContext, HealingState,
+    StateTransition,
+};
+
+const DEFAULT_EMBEDDING_DIMENSIONS: u32 = 1536;
+const HEALING_TIMEOUT_SECONDS: i64 = 600;
+const KNOWLEDGE_GRACE_SECONDS: i64 = 300;
+const POLICY_DEBOUNCE_SECONDS: u64 = 5;
 
 pub struct ReconcilerContext {
     pub client: Client,
     pub config: AppConfig,
-    pub event_bus: EventBus,
-    pub containment_agent: Arc<ContainmentAgent>,
-    pub diagnosis_agent: Arc<DiagnosisAgent>,
-    pub metacognitive_agent: Arc<MetaCognitiveAgent>,
-    pub knowledge_agent: Arc<KnowledgeAgent>,
+    pub prometheus: Arc<PrometheusClient>,
+    pub loki: Arc<LokiClient>,
+    pub redis: Arc<RedisClient>,
+    pub llm_clients: LlmClientCache,
+    pub health_checks: HealthCache,
+    pub healing_tracker: Arc<HealingTracker>,
+    pub policy_agents: RwLock<HashMap<String, Arc<PolicyAgents>>>,
+    qdrant_clients: RwLock<HashMap<u32, Arc<QdrantClient>>>,
 }
 
 impl ReconcilerContext {
@@ -43,114 +63,161 @@ impl ReconcilerContext {
             .await
             .map_err(|e| RecistError::KubeError(e))?;
 
-        let event_bus = EventBus::new();
-
         let prometheus = Arc::new(PrometheusClient::new(&config.prometheus)?);
         let loki = Arc::new(LokiClient::new(&config.loki)?);
-        let qdrant = Arc::new(QdrantClient::new(&config.qdrant, 1536).await?);
+        let qdrant =
+            Arc::new(QdrantClient::new(&config.qdrant, DEFAULT_EMBEDDING_DIMENSIONS).await?);
         let redis = Arc::new(RedisClient::new(&config.redis).await?);
 
-        let dummy_llm: Arc<dyn LlmClient> = Arc::new(DummyLlmClient);
-
-        let containment_agent = Arc::new(
-            ContainmentAgent::new(
-                prometheus.clone(),
-                event_bus.clone(),
-                Default::default(),
-                Default::default(),
-            )
-            .await?,
-        );
-
-        let diagnosis_agent = Arc::new(
-            DiagnosisAgent::new(
-                prometheus.clone(),
-                loki.clone(),
-                dummy_llm.clone(),
-                event_bus.clone(),
-                Default::default(),
-            )
-            .await?,
-        );
+        let llm_clients = LlmClientCache::new(client.clone());
 
-        let metacognitive_agent = Arc::new(
-            MetaCognitiveAgent::new(dummy_llm.clone(), event_bus.clone(), Default::default())
-                .await?,
-        );
-
-        let knowledge_agent = Arc::new(
-            KnowledgeAgent::new(
-                qdrant.clone(),
-                redis.clone(),
-                dummy_llm.clone(),
-                event_bus.clone(),
-                Default::default(),
-                config.namespace.clone(),
-            )
-            .await?,
-        );
+        let mut qdrant_clients = HashMap::new();
+        qdrant_clients.insert(DEFAULT_EMBEDDING_DIMENSIONS, qdrant);
 
         Ok(Self {
             client,
             config,
-            event_bus,
-            containment_agent,
-            diagnosis_agent,
-            metacognitive_agent,
-            knowledge_agent,
+            prometheus,
+            loki,
+            redis,
+            llm_clients,
+            health_checks: HealthCache::new(),
+            healing_tracker: Arc::new(HealingTracker::new()),
+            policy_agents: RwLock::new(HashMap::new()),
+            qdrant_clients: RwLock::new(qdrant_clients),
         })
     }
-}
 
-struct DummyLlmClient;
+    pub async fn qdrant_for_dimensions(&self, dimensions: u32) -> Result<Arc<QdrantClient>> {
+        if let Some(qdrant) = self.qdrant_clients.read().await.get(&dimensions) {
+            return Ok(qdrant.clone());
+        }
 
-#[async_trait::async_trait]
-impl LlmClient for DummyLlmClient {
-    async fn complete(&self, _prompt: &str) -> Result<String> {
-        Ok("{}".to_string())
-    }
+        let mut qdrant_config = self.config.qdrant.clone();
+        qdrant_config.collection_name =
+            format!("{}_{}", self.config.qdrant.collection_name, dimensions);
+
+        let qdrant = Arc::new(QdrantClient::new(&qdrant_config, dimensions).await?);
+        self.qdrant_clients
+            .write()
+            .await
+            .insert(dimensions, qdrant.clone());
 
-    async fn complete_with_system(&self, _system: &str, _prompt: &str) -> Result<String> {
-        Ok("{}".to_string())
+        Ok(qdrant)
     }
 
-    async fn diagnose(
+    pub async fn ensure_policy_agents(
         &self,
-        _request: &crate::clients::llm::DiagnosisRequest,
-    ) -> Result<crate::models::LlmDiagnosisResponse> {
-        Ok(crate::models::LlmDiagnosisResponse {
-            root_cause: "Unknown".to_string(),
-            confidence: 0.5,
-            evidence: vec![],
-            explanation: "Dummy response".to_string(),
-            suggested_actions: vec![],
-        })
+        policy: &SelfHealingPolicy,
+    ) -> Result<Arc<PolicyAgents>> {
+        let key = policy_key(policy);
+        let generation = policy.metadata.generation.unwrap_or(0);
+
+        if let Some(agents) = self.policy_agents.read().await.get(&key) {
+            if agents.generation == generation {
+                return Ok(agents.clone());
+            }
+        }
+
+        let agents = Arc::new(PolicyAgents::new(self, policy).await?);
+
+        let previous = {
+            let mut policy_agents = self.policy_agents.write().await;
+            match policy_agents.get(&key) {
+                Some(current) if current.generation >= generation => {
+                    return Ok(current.clone());
+                }
+                _ => policy_agents.insert(key.clone(), agents.clone()),
+            }
+        };
+
+        if let Some(previous) = previous {
+            info!(
+                "Policy {} changed from generation {} to {}, reconfiguring agents",
+                key, previous.generation, generation
+            );
+            previous.shutdown().await;
+        }
+        agents.start().await;
+
+        Ok(agents)
     }
 
-    async fn evaluate_strategy(
-        &self,
-        _request: &crate::clients::llm::StrategyEvaluationRequest,
-    ) -> Result<crate::models::StrategyEvaluation> {
-        Ok(crate::models::StrategyEvaluation {
-            strategy_type: crate::models::StrategyType::PodRestart,
-            success_probability: 0.7,
-            risk_score: 0.3,
-            estimated_time_seconds: 30,
-            reasoning: "Dummy evaluation".to_string(),
-            prerequisites_met: true,
-        })
+    pub async fn teardown_policy_agents(&self, key: &str) {
+        if let Some(agents) = self.policy_agents.write().await.remove(key) {
+            agents.shutdown().await;
+        }
+        self.llm_clients.evict(key).await;
+        self.health_checks.evict(&format!("llm/{}", key)).await;
     }
 
-    async fn generate_embedding(&self, _text: &str) -> Result<Vec<f32>> {
-        Ok(vec![0.0; 1536])
+    pub async fn dependency_conditions(&self, policy: &SelfHealingPolicy) -> Vec<PolicyCondition> {
+        let mut conditions = vec![
+            self.health_checks
+                .check("prometheus", self.prometheus.health_check())
+                .await
+                .to_condition("PrometheusReachable"),
+            self.health_checks
+                .check("loki", self.loki.health_check())
+                .await
+                .to_condition("LokiReachable"),
+            self.health_checks
+                .check("redis", self.redis.ping())
+                .await
+                .to_condition("RedisReachable"),
+        ];
+
+        let dimensions = policy.spec.knowledge_config.embedding_dimensions;
+        let qdrant_health = match self.qdrant_for_dimensions(dimensions).await {
+            Ok(qdrant) => {
+                self.health_checks
+                    .check(&format!("qdrant/{}", dimensions), qdrant.health_check())
+                    .await
+            }
+            Err(e) => DependencyHealth::unhealthy(e.to_string()),
+        };
+        conditions.push(qdrant_health.to_condition("QdrantReachable"));
+
+        conditions
     }
 
-    fn provider_name(&self) -> &str {
-        "Dummy"
+    pub async fn healing_events_for(
+        &self,
+        policy: &SelfHealingPolicy,
+    ) -> Result<Vec<HealingEvent>> {
+        let api: Api<HealingEvent> = Api::all(self.client.clone());
+        let selector = format!(
+            "{}={},{}={}",
+            POLICY_NAME_LABEL,
+            policy.name_any(),
+            POLICY_NAMESPACE_LABEL,
+            policy.namespace().unwrap_or_default()
+        );
+
+        let events = api.list(&ListParams::default().labels(&selector)).await?;
+        Ok(events.items)
     }
 
-    fn model_name(&self) -> &str {
-        "dummy-model"
+    pub async fn prune_policy_agents(&self, live_keys: &BTreeSet<String>) {
+        let stale: Vec<String> = self
+            .policy_agents
+            .read()
+            .await
+            .keys()
+            .filter(|key| !live_keys.contains(*key))
+            .cloned()
+            .collect();
+
+        for key in stale {
+            info!("Policy {} no longer exists, tearing down agents", key);
+            self.teardown_policy_agents(&key).await;
+        }
+    }
+
+    pub async fn shutdown(&self) {
+        for (_, agents) in self.policy_agents.write().await.drain() {
+            agents.shutdown().await;
+        }
     }
 }
 
@@ -160,34 +227,198 @@ pub async fn reconcile_policy(
 ) -> std::result::Result<Action, kube::runtime::controller::Error<RecistError>> {
     let name = policy.name_any();
     let namespace = policy.namespace().unwrap_or_default();
+    let key = policy_key(&policy);
 
     debug!("Reconciling SelfHealingPolicy {}/{}", namespace, name);
 
     let api: Api<SelfHealingPolicy> = Api::namespaced(ctx.client.clone(), &namespace);
 
-    let status = SelfHealingPolicyStatus {
+    let mut conditions = Vec::new();
+
+    let agents = match ctx.ensure_policy_agents(&policy).await {
+        Ok(agents) => Some(agents),
+        Err(e) => {
+            error!(
+                "Failed to start agents for policy {}/{}: {}",
+                namespace, name, e
+            );
+            conditions.push(failed_condition(
+                "Ready",
+                "AgentsUnavailable",
+                e.to_string(),
+            ));
+            None
+        }
+    };
+
+    if let Some(agents) = &agents {
+        match ctx.llm_clients.get_or_build(&policy).await {
+            Ok(llm) => {
+                agents.llm.set(llm.clone()).await;
+                agents.resume_containment().await;
+                let health = ctx
+                    .health_checks
+                    .check(&format!("llm/{}", key), llm.health_check())
+                    .await;
+                conditions.push(health.to_condition("LlmReachable"));
+            }
+            Err(e) => {
+                warn!(
+                    "Failed to build LLM client for policy {}/{}: {}",
+                    namespace, name, e
+                );
+                agents.llm.clear().await;
+                agents.pause_containment().await;
+                conditions.push(failed_condition(
+                    "Ready",
+                    "LlmClientUnavailable",
+                    e.to_string(),
+                ));
+            }
+        }
+    }
+
+    conditions.extend(ctx.dependency_conditions(&policy).await);
+
+    if !conditions.iter().any(|c| c.condition_type == "Ready") {
+        let unhealthy: Vec<&str> = conditions
+            .iter()
+            .filter(|c| c.status != "True")
+            .map(|c| c.condition_type.as_str())
+            .collect();
+
+        let ready = if unhealthy.is_empty() {
+            PolicyCondition {
+                condition_type: "Ready".to_string(),
+                status: "True".to_string(),
+                last_transition_time: Utc::now().to_rfc3339(),
+                reason: Some("Reconciled".to_string()),
+                message: Some(format!(
+                    "Policy is ready and monitoring namespaces {}",
+                    agents
+                        .as_ref()
+                        .map(|a| a.target_namespaces.join(", "))
+                        .unwrap_or_default()
+                )),
+            }
+        } else {
+            failed_condition(
+                "Ready",
+                "DependenciesUnavailable",
+                format!("Unhealthy dependencies: {}", unhealthy.join(", ")),
+            )
+        };
+        conditions.insert(0, ready);
+    }
+
+    let previous_conditions = policy
+        .status
+        .as_ref()
+        .map(|s| s.conditions.as_slice())
+        .unwrap_or_default();
+    for condition in conditions.iter_mut() {
+        if let Some(previous) = previous_conditions
+            .iter()
+            .find(|p| p.condition_type == condition.condition_type && p.status == condition.status)
+        {
+            condition.last_transition_time = previous.last_transition_time.clone();
+        }
+    }
+
+    let mut status = SelfHealingPolicyStatus {
         observed_generation: policy.metadata.generation.unwrap_or(0),
-        active_healings: 0,
-        last_healing_time: None,
-        total_healings: 0,
-        successful_healings: 0,
-        conditions: vec![PolicyCondition {
-            condition_type: "Ready".to_string(),
-            status: "True".to_string(),
-            last_transition_time: Utc::now().to_rfc3339(),
-            reason: Some("Reconciled".to_string()),
-            message: Some("Policy is ready and monitoring".to_string()),
-        }],
+        conditions,
+        ..Default::default()
     };
 
+    match ctx.healing_events_for(&policy).await {
+        Ok(events) => summarize_healing_events(&mut status, &events),
+        Err(e) => {
+            warn!(
+                "Failed to list HealingEvents for policy {}/{}: {}",
+                namespace, name, e
+            );
+            if let Some(previous) = &policy.status {
+                status.active_healings = previous.active_healings;
+                status.total_healings = previous.total_healings;
+                status.successful_healings = previous.successful_healings;
+                status.success_ratio = previous.success_ratio;
+                status.last_healing_time = previous.last_healing_time.clone();
+            }
+        }
+    }
+
     let patch = serde_json::json!({ "status": status });
-    let _ = api
+    if let Err(e) = api
         .patch_status(&name, &PatchParams::default(), &Patch::Merge(&patch))
-        .await;
+        .await
+    {
+        warn!(
+            "Failed to update SelfHealingPolicy {}/{} status: {}",
+            namespace, name, e
+        );
+    }
 
     Ok(Action::requeue(Duration::from_secs(300)))
 }
 
+fn failed_condition(condition_type: &str, reason: &str, message: String) -> PolicyCondition {
+    PolicyCondition {
+        condition_type: condition_type.to_string(),
+        status: "False".to_string(),
+        last_transition_time: Utc::now().to_rfc3339(),
+        reason: Some(reason.to_string()),
+        message: Some(message),
+    }
+}
+
+fn summarize_healing_events(status: &mut SelfHealingPolicyStatus, events: &[HealingEvent]) {
+    let phases: Vec<HealingPhase> = events
+        .iter()
+        .map(|e| {
+            e.status
+                .as_ref()
+                .map(|s| s.phase.clone())
+                .unwrap_or_default()
+        })
+        .collect();
+
+    status.total_healings = events.len() as i64;
+    status.active_healings = phases
+        .iter()
+        .filter(|p| !matches!(p, HealingPhase::Completed | HealingPhase::Failed))
+        .count() as i32;
+
+    let finished = phases
+        .iter()
+        .filter(|p| matches!(p, HealingPhase::Completed | HealingPhase::Failed))
+        .count() as i64;
+    status.successful_healings = events
+        .iter()
+        .filter_map(|e| e.status.as_ref())
+        .filter(|s| s.phase == HealingPhase::Completed)
+        .filter(|s| s.outcome.as_ref().map(|o| o.success).unwrap_or(true))
+        .count() as i64;
+    status.success_ratio = if finished > 0 {
+        Some(status.successful_healings as f64 / finished as f64)
+    } else {
+        None
+    };
+
+    status.last_healing_time = events
+        .iter()
+        .filter_map(|e| e.status.as_ref())
+        .filter_map(|s| s.start_time.as_deref().and_then(parse_time))
+        .max()
+        .map(|time| time.to_rfc3339());
+}
+
+fn healing_phase_hash(event: &HealingEvent) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    event.status.as_ref().map(|s| &s.phase).hash(&mut hasher);
+    Some(hasher.finish())
+}
+
 pub fn policy_error(
     policy: Arc<SelfHealingPolicy>,
     error: &kube::runtime::controller::Error<RecistError>,
@@ -209,45 +440,366 @@ pub async fn reconcile_event(
 
     let api: Api<HealingEvent> = Api::namespaced(ctx.client.clone(), &namespace);
 
-    let current_phase = event
+    let correlation_id = match event
+        .labels()
+        .get(CORRELATION_ID_LABEL)
+        .and_then(|id| Uuid::parse_str(id).ok())
+    {
+        Some(correlation_id) => correlation_id,
+        None => {
+            debug!(
+                "HealingEvent {}/{} has no correlation id, not driven by agents",
+                namespace, name
+            );
+            return Ok(Action::await_change());
+        }
+    };
+
+    let progress = ctx.healing_tracker.progress(&correlation_id).await;
+
+    let mut status = event.status.clone().unwrap_or_default();
+    let now = Utc::now();
+
+    let start_time = match status.start_time.as_deref().and_then(parse_time) {
+        Some(start_time) => start_time,
+        None => {
+            let created_at = event
+                .metadata
+                .creation_timestamp
+                .as_ref()
+                .map(|time| time.0)
+                .unwrap_or(now);
+            status.start_time = Some(created_at.to_rfc3339());
+            created_at
+        }
+    };
+
+    let mut state = HealingState::from_history(
+        start_time,
+        status
+            .transitions
+            .iter()
+            .map(StateTransition::from)
+            .collect(),
+    );
+
+    let recorded_transitions = state.transitions.len();
+    let mut transition_result = Ok(());
+
+    if status.phase != state.phase {
+        let requested = status.phase.clone();
+        status.phase = state.phase.clone();
+        transition_result =
+            state.transition_to(requested, Some("Status updated externally".to_string()));
+    }
+
+    if transition_result.is_ok() {
+        transition_result =
+            advance_healing_state(&mut state, &mut status, &event.spec, progress.as_ref());
+    }
+
+    if transition_result.is_ok()
+        && !state.is_terminal()
+        && (now - start_time).num_seconds() > HEALING_TIMEOUT_SECONDS
+    {
+        warn!(
+            "HealingEvent {}/{} timed out in phase {}",
+            namespace, name, state.phase
+        );
+        let message = format!("Healing timed out in phase {}", state.phase);
+        transition_result = state.transition_to(HealingPhase::Failed, Some(message.clone()));
+        status.outcome = Some(HealingOutcome {
+            success: false,
+            message,
+            verification_method: None,
+            metrics_after: None,
+        });
+    }
+
+    match &transition_result {
+        Ok(()) if state.transitions.len() > recorded_transitions => set_condition(
+            &mut status.conditions,
+            "TransitionValid",
+            "True",
+            "TransitionAccepted",
+            None,
+        ),
+        Ok(()) => {}
+        Err(e) => {
+            warn!(
+                "Rejected phase transition for HealingEvent {}/{}: {}",
+                namespace, name, e
+            );
+            set_condition(
+                &mut status.conditions,
+                "TransitionValid",
+                "False",
+                "InvalidTransition",
+                Some(e.to_string()),
+            );
+        }
+    }
+
+    if let Some(progress) = &progress {
+        if status.knowledge_entry_id.is_none() {
+            status.knowledge_entry_id = progress.knowledge_entry_id.map(|id| id.to_string());
+        }
+
+        if let Some(reason) = progress.refusal(&event.spec.target_pod) {
+            set_condition(
+                &mut status.conditions,
+                "ActionRefused",
+                "True",
+                "AvailabilityGuard",
+                Some(reason.to_string()),
+            );
+        }
+
+        if let Some(reason) = progress.downgrade(&event.spec.target_pod) {
+            set_condition(
+                &mut status.conditions,
+                "IsolationDowngraded",
+                "True",
+                "AvailabilityGuard",
+                Some(reason.to_string()),
+            );
+        }
+    }
+
+    status.phase = state.phase.clone();
+    status.transitions = state
+        .transitions
+        .iter()
+        .map(PhaseTransition::from)
+        .collect();
+    status.phase_timings = phase_timings(&state);
+
+    if state.is_terminal() && status.end_time.is_none() {
+        status.end_time = Some(state.updated_at.to_rfc3339());
+        status.duration_ms = Some(state.duration_ms());
+    }
+
+    let changed = event
         .status
         .as_ref()
-        .map(|s| s.phase.clone())
-        .unwrap_or(HealingPhase::Pending);
-
-    if matches!(
-        current_phase,
-        HealingPhase::Completed | HealingPhase::Failed
-    ) {
-        return Ok(Action::await_change());
+        .map(|current| serde_json::to_value(current).ok() != serde_json::to_value(&status).ok())
+        .unwrap_or(true);
+
+    if changed {
+        let patch = serde_json::json!({ "status": status });
+        if let Err(e) = api
+            .patch_status(&name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+        {
+            warn!(
+                "Failed to update HealingEvent {}/{} status: {}",
+                namespace, name, e
+            );
+        }
     }
 
-    let next_phase = match current_phase {
-        HealingPhase::Pending => HealingPhase::Containing,
-        HealingPhase::Containing => HealingPhase::Diagnosing,
-        HealingPhase::Diagnosing => HealingPhase::Healing,
-        HealingPhase::Healing => HealingPhase::Verifying,
-        HealingPhase::Verifying => HealingPhase::Completed,
-        _ => return Ok(Action::await_change()),
-    };
+    if !state.is_terminal() {
+        return Ok(Action::requeue(Duration::from_secs(2)));
+    }
 
-    let mut status = event.status.clone().unwrap_or_default();
-    status.phase = next_phase.clone();
+    let awaiting_knowledge = status.knowledge_entry_id.is_none()
+        && status
+            .end_time
+            .as_deref()
+            .and_then(parse_time)
+            .map(|end_time| (now - end_time).num_seconds() < KNOWLEDGE_GRACE_SECONDS)
+            .unwrap_or(false);
+
+    if awaiting_knowledge {
+        Ok(Action::requeue(Duration::from_secs(10)))
+    } else {
+        Ok(Action::await_change())
+    }
+}
+
+fn advance_healing_state(
+    state: &mut HealingState,
+    status: &mut HealingEventStatus,
+    spec: &HealingEventSpec,
+    progress: Option<&HealingProgress>,
+) -> Result<()> {
+    loop {
+        let (next_phase, reason, timestamp) = match (state.phase.clone(), progress) {
+            (HealingPhase::Pending, _) => (
+                HealingPhase::Containing,
+                "Containment started".to_string(),
+                state.created_at,
+            ),
+            (HealingPhase::Containing, Some(progress)) => {
+                match progress.contained_at(&spec.target_pod) {
+                    Some(contained_at) => (
+                        HealingPhase::Diagnosing,
+                        format!("Pod {} contained", spec.target_pod),
+                        contained_at,
+                    ),
+                    None => break,
+                }
+            }
+            (HealingPhase::Diagnosing, Some(progress)) => {
+                match (&progress.diagnosis, progress.diagnosed_at) {
+                    (Some(hypothesis), Some(diagnosed_at)) => {
+                        status.diagnosis = Some(hypothesis.into());
+                        status.causal_graph = Some((&hypothesis.causal_tree).into());
+                        (
+                            HealingPhase::Healing,
+                            format!("Diagnosed root cause: {}", hypothesis.root_cause),
+                            diagnosed_at,
+                        )
+                    }
+                    _ => break,
+                }
+            }
+            (HealingPhase::Healing, Some(progress)) => {
+                match (&progress.healing, progress.healed_at) {
+                    (Some(healing), Some(healed_at)) => {
+                        status.applied_actions = applied_actions(healing);
+                        (
+                            HealingPhase::Verifying,
+                            format!("Applied {} strategy", healing.strategy.strategy_type),
+                            healing.verification_started_at.unwrap_or(healed_at),
+                        )
+                    }
+                    _ => break,
+                }
+            }
+            (HealingPhase::Verifying, Some(progress)) => {
+                match (&progress.healing, progress.healed_at) {
+                    (Some(healing), Some(healed_at)) => {
+                        status.outcome = Some(HealingOutcome {
+                            success: healing.success,
+                            message: healing.message.clone(),
+                            verification_method: Some("PodReadiness".to_string()),
+                            metrics_after: None,
+                        });
+                        if healing.success {
+                            (
+                                HealingPhase::Completed,
+                                "Verification passed".to_string(),
+                                healed_at,
+                            )
+                        } else {
+                            (HealingPhase::Failed, healing.message.clone(), healed_at)
+                        }
+                    }
+                    _ => break,
+                }
+            }
+            _ => break,
+        };
 
-    if status.start_time.is_none() {
-        status.start_time = Some(Utc::now().to_rfc3339());
+        state.transition_at(next_phase, Some(reason), timestamp)?;
     }
 
-    if matches!(next_phase, HealingPhase::Completed | HealingPhase::Failed) {
-        status.end_time = Some(Utc::now().to_rfc3339());
+    Ok(())
+}
+
+fn phase_timings(state: &HealingState) -> Vec<PhaseTiming> {
+    state
+        .phase_durations()
+        .into_iter()
+        .filter_map(|(phase, duration_ms)| {
+            phase.target_duration_ms().map(|target_ms| PhaseTiming {
+                phase,
+                duration_ms,
+                target_ms,
+                within_target: duration_ms <= target_ms,
+            })
+        })
+        .collect()
+}
+
+fn set_condition(
+    conditions: &mut Vec<HealingEventCondition>,
+    condition_type: &str,
+    status: &str,
+    reason: &str,
+    message: Option<String>,
+) {
+    let now = Utc::now().to_rfc3339();
+
+    match conditions
+        .iter_mut()
+        .find(|c| c.condition_type == condition_type)
+    {
+        Some(condition) => {
+            if condition.status != status {
+                condition.last_transition_time = now;
+            }
+            condition.status = status.to_string();
+            condition.reason = Some(reason.to_string());
+            condition.message = message;
+        }
+        None => conditions.push(HealingEventCondition {
+            condition_type: condition_type.to_string(),
+            status: status.to_string(),
+            last_transition_time: now,
+            reason: Some(reason.to_string()),
+            message,
+        }),
     }
+}
 
-    let patch = serde_json::json!({ "status": status });
-    let _ = api
-        .patch_status(&name, &PatchParams::default(), &Patch::Merge(&patch))
-        .await;
+fn applied_actions(healing: &HealingCompletePayload) -> Vec<AppliedAction> {
+    let overall = if healing.success {
+        ActionResult::Success
+    } else {
+        ActionResult::Failed
+    };
 
-    Ok(Action::requeue(Duration::from_secs(5)))
+    healing
+        .strategy
+        .actions
+        .iter()
+        .map(|action| {
+            let executed = healing
+                .action_results
+                .iter()
+                .find(|result| result.order == action.order);
+
+            let target = format!(
+                "{:?} {}/{}",
+                action.target.resource_type, action.target.namespace, action.target.name
+            );
+
+            match executed {
+                Some(executed) => AppliedAction {
+                    action_type: action.action_type.clone(),
+                    timestamp: executed.executed_at.to_rfc3339(),
+                    result: if executed.rolled_back {
+                        ActionResult::RolledBack
+                    } else if executed.success {
+                        ActionResult::Success
+                    } else {
+                        ActionResult::Failed
+                    },
+                    details: Some(format!("{}: {}", target, executed.message)),
+                    rollback_info: executed.rollback_data.clone(),
+                },
+                None => AppliedAction {
+                    action_type: action.action_type.clone(),
+                    timestamp: healing.strategy.selected_at.to_rfc3339(),
+                    result: if healing.action_results.is_empty() {
+                        overall.clone()
+                    } else {
+                        ActionResult::Pending
+                    },
+                    details: Some(target),
+                    rollback_info: None,
+                },
+            }
+        })
+        .collect()
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
 }
 
 pub fn event_error(
@@ -265,8 +817,53 @@ pub async fn run_controllers(ctx: Arc<ReconcilerContext>) -> Result<()> {
 
     let policy_api: Api<SelfHealingPolicy> = Api::all(ctx.client.clone());
     let event_api: Api<HealingEvent> = Api::all(ctx.client.clone());
-
-    let policy_controller = Controller::new(policy_api, WatcherConfig::default())
+    let secret_api: Api<Secret> = Api::all(ctx.client.clone());
+
+    let policy_lifecycle = watch_policy_lifecycle(policy_api.clone(), ctx.clone());
+
+    let policy_controller = Controller::new(policy_api, WatcherConfig::default());
+    let policy_store = policy_controller.store();
+
+    let policy_controller = policy_controller
+        .watches_stream(
+            metadata_watcher(secret_api, WatcherConfig::default()).touched_objects(),
+            move |secret| {
+                let secret_namespace = secret.namespace();
+                let secret_name = secret.name_any();
+                policy_store
+                    .state()
+                    .into_iter()
+                    .filter(|policy| {
+                        policy.namespace() == secret_namespace
+                            && policy.spec.llm_config.api_key_secret == secret_name
+                    })
+                    .map(|policy| ObjectRef::from_obj(policy.as_ref()))
+                    .collect::<Vec<_>>()
+            },
+        )
+        .watches_stream(
+            watcher(
+                event_api.clone(),
+                WatcherConfig::default().labels(POLICY_NAME_LABEL),
+            )
+            .touched_objects()
+            .predicate_filter(healing_phase_hash),
+            |event| {
+                let labels = event.labels();
+                match (
+                    labels.get(POLICY_NAME_LABEL),
+                    labels.get(POLICY_NAMESPACE_LABEL),
+                ) {
+                    (Some(name), Some(namespace)) => {
+                        Some(ObjectRef::<SelfHealingPolicy>::new(name).within(namespace))
+                    }
+                    _ => None,
+                }
+            },
+        )
+        .with_config(
+            ControllerConfig::default().debounce(Duration::from_secs(POLICY_DEBOUNCE_SECONDS)),
+        )
         .run(reconcile_policy, policy_error, ctx.clone())
         .for_each(|res| async move {
             match res {
@@ -291,7 +888,93 @@ pub async fn run_controllers(ctx: Arc<ReconcilerContext>) -> Result<()> {
         _ = event_controller => {
             info!("Event controller stopped");
         }
+        _ = policy_lifecycle => {
+            info!("Policy lifecycle watcher stopped");
+        }
     }
 
+    ctx.shutdown().await;
+
     Ok(())
 }
+
+async fn watch_policy_lifecycle(api: Api<SelfHealingPolicy>, ctx: Arc<ReconcilerContext>) {
+    let mut events = watcher(api, WatcherConfig::default()).boxed();
+
+    while let Some(event) = events.next().await {
+        match event {
+            Ok(WatcherEvent::Deleted(policy)) => {
+                let key = policy_key(&policy);
+                info!("Policy {} deleted, tearing down agents", key);
+                ctx.teardown_policy_agents(&key).await;
+            }
+            Ok(WatcherEvent::Restarted(policies)) => {
+                let live_keys: BTreeSet<String> = policies.iter().map(policy_key).collect();
+                ctx.prune_policy_agents(&live_keys).await;
+            }
+            Ok(WatcherEvent::Applied(_)) => {}
+            Err(e) => {
+                warn!("Policy lifecycle watch error: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crd::{HealingEventSpec, TriggerReason};
+
+    fn healing_event(phase: Option<HealingPhase>, success: bool, start_time: &str) -> HealingEvent {
+        let mut event = HealingEvent::new(
+            "web-1-heal",
+            HealingEventSpec {
+                policy_ref: "default/policy".to_string(),
+                target_pod: "web-1".to_string(),
+                target_namespace: "default".to_string(),
+                trigger_reason: TriggerReason::HighErrorRate,
+                trigger_metrics: None,
+            },
+        );
+        event.status = phase.map(|phase| HealingEventStatus {
+            phase,
+            start_time: Some(start_time.to_string()),
+            outcome: Some(HealingOutcome {
+                success,
+                message: String::new(),
+                verification_method: None,
+                metrics_after: None,
+            }),
+            ..Default::default()
+        });
+        event
+    }
+
+    #[test]
+    fn test_summarize_healing_events() {
+        let events = vec![
+            healing_event(Some(HealingPhase::Completed), true, "2026-01-01T00:00:00Z"),
+            healing_event(Some(HealingPhase::Completed), false, "2026-01-01T00:05:00Z"),
+            healing_event(Some(HealingPhase::Failed), false, "2026-01-01T00:10:00Z"),
+            healing_event(Some(HealingPhase::Healing), false, "2026-01-01T00:20:00Z"),
+            healing_event(None, false, ""),
+        ];
+
+        let mut status = SelfHealingPolicyStatus::default();
+        summarize_healing_events(&mut status, &events);
+
+        assert_eq!(status.total_healings, 5);
+        assert_eq!(status.active_healings, 2);
+        assert_eq!(status.successful_healings, 1);
+        assert_eq!(status.success_ratio, Some(1.0 / 3.0));
+        assert_eq!(
+            status.last_healing_time.as_deref().and_then(parse_time),
+            parse_time("2026-01-01T00:20:00Z")
+        );
+
+        let mut empty = SelfHealingPolicyStatus::default();
+        summarize_healing_events(&mut empty, &[]);
+        assert_eq!(empty.total_healings, 0);
+        assert_eq!(empty.success_ratio, None);
+    }
+}
@@ -2,6 +2,12 @@ use kube::CustomResource;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use super::BaselineMethod;
+
+pub const CORRELATION_ID_LABEL: &str = "recist.io/correlation-id";
+pub const POLICY_NAME_LABEL: &str = "recist.io/policy-name";
+pub const POLICY_NAMESPACE_LABEL: &str = "recist.io/policy-namespace";
+
 #[derive(CustomResource, Clone, Debug, Deserialize, Serialize, JsonSchema)]
 #[kube(
     group = "recist.io",
@@ -39,6 +45,8 @@ pub enum TriggerReason {
     OomKilled,
     NetworkError,
     DependencyFailure,
+    ImagePullBackOff,
+    ProbeFailure,
     Unknown,
 }
 
@@ -53,6 +61,8 @@ impl std::fmt::Display for TriggerReason {
             TriggerReason::OomKilled => write!(f, "OomKilled"),
             TriggerReason::NetworkError => write!(f, "NetworkError"),
             TriggerReason::DependencyFailure => write!(f, "DependencyFailure"),
+            TriggerReason::ImagePullBackOff => write!(f, "ImagePullBackOff"),
+            TriggerReason::ProbeFailure => write!(f, "ProbeFailure"),
             TriggerReason::Unknown => write!(f, "Unknown"),
         }
     }
@@ -71,6 +81,19 @@ pub struct TriggerMetrics {
     pub error_rate: Option<f64>,
     #[serde(default)]
     pub restart_count: Option<i32>,
+    #[serde(default)]
+    pub anomalies: Vec<AnomalyScore>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AnomalyScore {
+    pub metric: String,
+    pub value: f64,
+    pub baseline_mean: f64,
+    pub baseline_stddev: f64,
+    pub z_score: f64,
+    pub method: BaselineMethod,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
@@ -101,9 +124,18 @@ pub struct HealingEventStatus {
 
     #[serde(default)]
     pub knowledge_entry_id: Option<String>,
+
+    #[serde(default)]
+    pub transitions: Vec<PhaseTransition>,
+
+    #[serde(default)]
+    pub phase_timings: Vec<PhaseTiming>,
+
+    #[serde(default)]
+    pub conditions: Vec<HealingEventCondition>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq, Hash)]
 #[serde(rename_all = "PascalCase")]
 pub enum HealingPhase {
     #[default]
@@ -130,6 +162,50 @@ impl std::fmt::Display for HealingPhase {
     }
 }
 
+impl HealingPhase {
+    pub fn target_duration_ms(&self) -> Option<i64> {
+        match self {
+            HealingPhase::Containing => Some(5_000),
+            HealingPhase::Diagnosing => Some(15_000),
+            HealingPhase::Healing => Some(20_000),
+            HealingPhase::Verifying => Some(5_000),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseTransition {
+    #[serde(default)]
+    pub from: Option<HealingPhase>,
+    pub to: HealingPhase,
+    pub timestamp: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PhaseTiming {
+    pub phase: HealingPhase,
+    pub duration_ms: i64,
+    pub target_ms: i64,
+    pub within_target: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HealingEventCondition {
+    pub condition_type: String,
+    pub status: String,
+    pub last_transition_time: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DiagnosisResult {
@@ -3,6 +3,13 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+use super::TriggerReason;
+
+pub const IGNORE_ANNOTATION: &str = "recist.io/ignore";
+pub const ISOLATED_LABEL: &str = "recist.io/isolated";
+pub const QUARANTINE_ANNOTATION: &str = "recist.io/quarantine";
+pub const REDIRECT_LABEL_PREFIX: &str = "redirect.recist.io/";
+
 #[derive(CustomResource, Clone, Debug, Deserialize, Serialize, JsonSchema)]
 #[kube(
     group = "recist.io",
@@ -12,7 +19,9 @@ use std::collections::BTreeMap;
     shortname = "shp",
     namespaced,
     status = "SelfHealingPolicyStatus",
+    printcolumn = r#"{"name":"Ready","type":"string","jsonPath":".status.conditions[?(@.conditionType==\"Ready\")].status"}"#,
     printcolumn = r#"{"name":"Active Healings","type":"integer","jsonPath":".status.activeHealings"}"#,
+    printcolumn = r#"{"name":"Success Ratio","type":"number","jsonPath":".status.successRatio"}"#,
     printcolumn = r#"{"name":"Last Healing","type":"date","jsonPath":".status.lastHealingTime"}"#,
     printcolumn = r#"{"name":"Age","type":"date","jsonPath":".metadata.creationTimestamp"}"#
 )]
@@ -24,8 +33,17 @@ pub struct SelfHealingPolicySpec {
     #[serde(default)]
     pub target_labels: BTreeMap<String, String>,
 
+    #[serde(default)]
+    pub target_label_expressions: Vec<LabelSelectorRequirement>,
+
     pub thresholds: Thresholds,
 
+    #[serde(default)]
+    pub signals: Vec<SignalTemplate>,
+
+    #[serde(default)]
+    pub rules: Vec<ThresholdRule>,
+
     #[serde(default)]
     pub allowed_actions: Vec<AllowedAction>,
 
@@ -47,6 +65,46 @@ pub struct SelfHealingPolicySpec {
     pub knowledge_config: KnowledgeConfig,
 }
 
+impl SelfHealingPolicySpec {
+    pub fn target_selector(&self) -> String {
+        let equality = self
+            .target_labels
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value));
+
+        let set_based = self
+            .target_label_expressions
+            .iter()
+            .map(|expr| match expr.operator {
+                LabelSelectorOperator::In => format!("{} in ({})", expr.key, expr.values.join(",")),
+                LabelSelectorOperator::NotIn => {
+                    format!("{} notin ({})", expr.key, expr.values.join(","))
+                }
+                LabelSelectorOperator::Exists => expr.key.clone(),
+                LabelSelectorOperator::DoesNotExist => format!("!{}", expr.key),
+            });
+
+        equality.chain(set_based).collect::<Vec<_>>().join(",")
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelSelectorRequirement {
+    pub key: String,
+    pub operator: LabelSelectorOperator,
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+pub enum LabelSelectorOperator {
+    In,
+    NotIn,
+    Exists,
+    DoesNotExist,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Thresholds {
@@ -61,6 +119,21 @@ pub struct Thresholds {
 
     #[serde(default = "default_error_rate_threshold")]
     pub error_rate: f64,
+
+    #[serde(default = "default_restart_count_threshold")]
+    pub restart_count: i32,
+
+    #[serde(default)]
+    pub for_seconds: u64,
+
+    #[serde(default = "default_hysteresis")]
+    pub hysteresis: f64,
+}
+
+impl Thresholds {
+    pub fn clear_value(&self, threshold: f64) -> f64 {
+        threshold * (1.0 - self.hysteresis.clamp(0.0, 1.0))
+    }
 }
 
 fn default_cpu_threshold() -> f64 {
@@ -75,6 +148,235 @@ fn default_latency_threshold() -> u64 {
 fn default_error_rate_threshold() -> f64 {
     0.05
 }
+fn default_restart_count_threshold() -> i32 {
+    3
+}
+fn default_hysteresis() -> f64 {
+    0.1
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalTemplate {
+    pub name: String,
+    pub query: String,
+    pub operator: ComparisonOperator,
+    pub threshold: f64,
+    #[serde(default)]
+    pub clear_threshold: Option<f64>,
+    #[serde(default)]
+    pub for_seconds: u64,
+    #[serde(default = "default_signal_reason")]
+    pub reason: TriggerReason,
+}
+
+impl SignalTemplate {
+    pub fn render(&self, namespace: &str, pod: &str) -> String {
+        render_query(&self.query, namespace, pod)
+    }
+
+    pub fn render_batch(&self, namespace: &str, pods: &[String]) -> Option<String> {
+        render_batch_query(&self.query, namespace, pods)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ThresholdRule {
+    pub name: String,
+    pub expression: String,
+    pub operator: ComparisonOperator,
+    pub value: f64,
+    #[serde(default)]
+    pub clear_value: Option<f64>,
+    #[serde(default)]
+    pub for_seconds: u64,
+    #[serde(default)]
+    pub severity: RuleSeverity,
+    #[serde(default = "default_signal_reason")]
+    pub reason: TriggerReason,
+}
+
+impl ThresholdRule {
+    pub fn render(&self, namespace: &str, pod: &str) -> String {
+        render_query(&self.expression, namespace, pod)
+    }
+
+    pub fn render_batch(&self, namespace: &str, pods: &[String]) -> Option<String> {
+        render_batch_query(&self.expression, namespace, pods)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RuleSeverity {
+    Low,
+    #[default]
+    Medium,
+    High,
+    Critical,
+}
+
+fn render_query(template: &str, namespace: &str, pod: &str) -> String {
+    template
+        .replace("{namespace}", namespace)
+        .replace("{pod}", pod)
+}
+
+const POD_MATCHER: &str = "pod=\"{pod}\"";
+
+const AGGREGATIONS: [&str; 9] = [
+    "sum", "min", "max", "avg", "count", "group", "stddev", "stdvar", "quantile",
+];
+
+// Renders a template for several pods at once: `pod="{pod}"` becomes a regex matcher and
+// every aggregation is grouped by pod so the result can be split by the pod label.
+// Templates using `{pod}` any other way, aggregating series not selected by pod or
+// grouping on their own are left to per-pod rendering.
+fn render_batch_query(template: &str, namespace: &str, pods: &[String]) -> Option<String> {
+    if pods.is_empty() || !template.contains("{pod}") {
+        return None;
+    }
+
+    let matchers = template
+        .match_indices(POD_MATCHER)
+        .filter(|(index, _)| {
+            template[..*index]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !(c.is_alphanumeric() || c == '_'))
+        })
+        .count();
+    if matchers != template.matches("{pod}").count() {
+        return None;
+    }
+
+    let pattern = pods
+        .iter()
+        .map(|pod| pod.replace('.', "\\\\."))
+        .collect::<Vec<_>>()
+        .join("|");
+    let query = group_by_pod(&template.replace("{namespace}", namespace))?;
+
+    Some(query.replace(POD_MATCHER, &format!("pod=~\"{}\"", pattern)))
+}
+
+fn group_by_pod(query: &str) -> Option<String> {
+    let mut grouped = String::with_capacity(query.len());
+    let mut index = 0;
+
+    while index < query.len() {
+        let rest = &query[index..];
+        let at_boundary = query[..index]
+            .chars()
+            .next_back()
+            .is_none_or(|c| !(c.is_alphanumeric() || c == '_'));
+        let aggregation = AGGREGATIONS.iter().filter(|_| at_boundary).find_map(|op| {
+            let args = rest.strip_prefix(*op)?;
+            let next = args.chars().next()?;
+            (!(next.is_alphanumeric() || next == '_')).then(|| (*op, args.trim_start()))
+        });
+
+        if let Some((op, args)) = aggregation {
+            if args.starts_with("by") || args.starts_with("without") {
+                return None;
+            }
+
+            if args.starts_with('(') {
+                let open = query.len() - args.len();
+                let close = matching_paren(query, open)?;
+                let after = query[close + 1..].trim_start();
+                if after.starts_with("by")
+                    || after.starts_with("without")
+                    || !query[open..close].contains(POD_MATCHER)
+                {
+                    return None;
+                }
+
+                grouped.push_str(op);
+                grouped.push_str(" by (pod) ");
+                index = open;
+                continue;
+            }
+        }
+
+        let c = rest.chars().next()?;
+        grouped.push(c);
+        index += c.len_utf8();
+    }
+
+    Some(grouped)
+}
+
+fn matching_paren(query: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (offset, c) in query[open..].char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '(' if !in_string => depth += 1,
+            ')' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + offset);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn default_signal_reason() -> TriggerReason {
+    TriggerReason::Unknown
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+pub enum ComparisonOperator {
+    #[serde(rename = ">")]
+    GreaterThan,
+    #[serde(rename = ">=")]
+    GreaterThanOrEqual,
+    #[serde(rename = "<")]
+    LessThan,
+    #[serde(rename = "<=")]
+    LessThanOrEqual,
+    #[serde(rename = "==")]
+    Equal,
+    #[serde(rename = "!=")]
+    NotEqual,
+}
+
+impl ComparisonOperator {
+    pub fn compare(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            ComparisonOperator::GreaterThan => value > threshold,
+            ComparisonOperator::GreaterThanOrEqual => value >= threshold,
+            ComparisonOperator::LessThan => value < threshold,
+            ComparisonOperator::LessThanOrEqual => value <= threshold,
+            ComparisonOperator::Equal => value == threshold,
+            ComparisonOperator::NotEqual => value != threshold,
+        }
+    }
+}
+
+impl std::fmt::Display for ComparisonOperator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComparisonOperator::GreaterThan => write!(f, ">"),
+            ComparisonOperator::GreaterThanOrEqual => write!(f, ">="),
+            ComparisonOperator::LessThan => write!(f, "<"),
+            ComparisonOperator::LessThanOrEqual => write!(f, "<="),
+            ComparisonOperator::Equal => write!(f, "=="),
+            ComparisonOperator::NotEqual => write!(f, "!="),
+        }
+    }
+}
 
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
@@ -95,6 +397,9 @@ pub struct LlmConfig {
 
     pub api_key_secret: String,
 
+    #[serde(default = "default_api_key_secret_key")]
+    pub api_key_secret_key: String,
+
     #[serde(default = "default_llm_timeout")]
     pub timeout_seconds: u64,
 
@@ -102,6 +407,10 @@ pub struct LlmConfig {
     pub base_url: Option<String>,
 }
 
+fn default_api_key_secret_key() -> String {
+    "key".to_string()
+}
+
 fn default_llm_timeout() -> u64 {
     30
 }
@@ -142,6 +451,24 @@ pub struct ContainmentConfig {
 
     #[serde(default = "default_neighbor_capacity_threshold")]
     pub neighbor_capacity_threshold: f64,
+
+    #[serde(default = "default_max_isolation_minutes")]
+    pub max_isolation_minutes: u64,
+
+    #[serde(default)]
+    pub traffic_routing: TrafficRouting,
+
+    #[serde(default = "default_correlation_window")]
+    pub correlation_window_seconds: u64,
+
+    #[serde(default)]
+    pub detection_mode: DetectionMode,
+
+    #[serde(default)]
+    pub anomaly_detection: AnomalyDetectionConfig,
+
+    #[serde(default)]
+    pub forecasting: ForecastConfig,
 }
 
 impl Default for ContainmentConfig {
@@ -150,6 +477,12 @@ impl Default for ContainmentConfig {
             check_interval_seconds: default_check_interval(),
             isolation_strategy: default_isolation_strategy(),
             neighbor_capacity_threshold: default_neighbor_capacity_threshold(),
+            max_isolation_minutes: default_max_isolation_minutes(),
+            traffic_routing: TrafficRouting::default(),
+            correlation_window_seconds: default_correlation_window(),
+            detection_mode: DetectionMode::default(),
+            anomaly_detection: AnomalyDetectionConfig::default(),
+            forecasting: ForecastConfig::default(),
         }
     }
 }
@@ -163,6 +496,12 @@ fn default_isolation_strategy() -> IsolationStrategy {
 fn default_neighbor_capacity_threshold() -> f64 {
     0.7
 }
+fn default_max_isolation_minutes() -> u64 {
+    60
+}
+fn default_correlation_window() -> u64 {
+    120
+}
 
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -170,6 +509,192 @@ pub enum IsolationStrategy {
     Soft,
     Hard,
     Auto,
+    Quarantine,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrafficRouting {
+    #[default]
+    None,
+    Istio,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DetectionMode {
+    #[default]
+    Threshold,
+    Anomaly,
+    Combined,
+}
+
+impl DetectionMode {
+    pub fn uses_thresholds(&self) -> bool {
+        matches!(self, DetectionMode::Threshold | DetectionMode::Combined)
+    }
+
+    pub fn uses_anomalies(&self) -> bool {
+        matches!(self, DetectionMode::Anomaly | DetectionMode::Combined)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AnomalyDetectionConfig {
+    #[serde(default)]
+    pub method: BaselineMethod,
+
+    #[serde(default = "default_anomaly_lookback")]
+    pub lookback_minutes: u64,
+
+    #[serde(default = "default_anomaly_step")]
+    pub step_seconds: u64,
+
+    #[serde(default = "default_z_score_threshold")]
+    pub z_score_threshold: f64,
+
+    #[serde(default = "default_ewma_alpha")]
+    pub ewma_alpha: f64,
+
+    #[serde(default = "default_min_samples")]
+    pub min_samples: usize,
+
+    #[serde(default = "default_consecutive_checks")]
+    pub consecutive_checks: u32,
+}
+
+impl Default for AnomalyDetectionConfig {
+    fn default() -> Self {
+        Self {
+            method: BaselineMethod::default(),
+            lookback_minutes: default_anomaly_lookback(),
+            step_seconds: default_anomaly_step(),
+            z_score_threshold: default_z_score_threshold(),
+            ewma_alpha: default_ewma_alpha(),
+            min_samples: default_min_samples(),
+            consecutive_checks: default_consecutive_checks(),
+        }
+    }
+}
+
+fn default_anomaly_lookback() -> u64 {
+    60
+}
+fn default_anomaly_step() -> u64 {
+    60
+}
+fn default_z_score_threshold() -> f64 {
+    3.0
+}
+fn default_ewma_alpha() -> f64 {
+    0.3
+}
+fn default_min_samples() -> usize {
+    10
+}
+fn default_consecutive_checks() -> u32 {
+    3
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub method: ForecastMethod,
+
+    #[serde(default = "default_forecast_lookback")]
+    pub lookback_minutes: u64,
+
+    #[serde(default = "default_forecast_step")]
+    pub step_seconds: u64,
+
+    #[serde(default = "default_forecast_horizon")]
+    pub horizon_minutes: u64,
+
+    #[serde(default = "default_smoothing_alpha")]
+    pub smoothing_alpha: f64,
+
+    #[serde(default = "default_smoothing_beta")]
+    pub smoothing_beta: f64,
+
+    #[serde(default = "default_forecast_min_samples")]
+    pub min_samples: usize,
+
+    #[serde(default)]
+    pub preemptive_scaling: bool,
+
+    #[serde(default = "default_preemptive_max_replicas")]
+    pub max_replicas: i32,
+}
+
+impl Default for ForecastConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            method: ForecastMethod::default(),
+            lookback_minutes: default_forecast_lookback(),
+            step_seconds: default_forecast_step(),
+            horizon_minutes: default_forecast_horizon(),
+            smoothing_alpha: default_smoothing_alpha(),
+            smoothing_beta: default_smoothing_beta(),
+            min_samples: default_forecast_min_samples(),
+            preemptive_scaling: false,
+            max_replicas: default_preemptive_max_replicas(),
+        }
+    }
+}
+
+fn default_forecast_lookback() -> u64 {
+    30
+}
+fn default_forecast_step() -> u64 {
+    60
+}
+fn default_forecast_horizon() -> u64 {
+    30
+}
+fn default_smoothing_alpha() -> f64 {
+    0.5
+}
+fn default_smoothing_beta() -> f64 {
+    0.3
+}
+fn default_preemptive_max_replicas() -> i32 {
+    10
+}
+fn default_forecast_min_samples() -> usize {
+    5
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ForecastMethod {
+    #[default]
+    Linear,
+    Holt,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, JsonSchema, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BaselineMethod {
+    #[default]
+    Rolling,
+    Seasonal,
+    Ewma,
+}
+
+impl std::fmt::Display for BaselineMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BaselineMethod::Rolling => write!(f, "rolling"),
+            BaselineMethod::Seasonal => write!(f, "seasonal"),
+            BaselineMethod::Ewma => write!(f, "ewma"),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
@@ -222,6 +747,15 @@ pub struct MetaCognitiveConfig {
 
     #[serde(default = "default_decision_threshold")]
     pub decision_threshold: f64,
+
+    #[serde(default = "default_max_composite_steps")]
+    pub max_composite_steps: u32,
+
+    #[serde(default = "default_max_composite_risk_score")]
+    pub max_composite_risk_score: f64,
+
+    #[serde(default)]
+    pub config_updates: BTreeMap<String, String>,
 }
 
 impl Default for MetaCognitiveConfig {
@@ -232,6 +766,9 @@ impl Default for MetaCognitiveConfig {
             action_timeout_seconds: default_action_timeout(),
             verification_wait_seconds: default_verification_wait(),
             decision_threshold: default_decision_threshold(),
+            max_composite_steps: default_max_composite_steps(),
+            max_composite_risk_score: default_max_composite_risk_score(),
+            config_updates: BTreeMap::new(),
         }
     }
 }
@@ -251,6 +788,12 @@ fn default_verification_wait() -> u64 {
 fn default_decision_threshold() -> f64 {
     0.7
 }
+fn default_max_composite_steps() -> u32 {
+    1
+}
+fn default_max_composite_risk_score() -> f64 {
+    0.3
+}
 
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
@@ -310,6 +853,9 @@ pub struct SelfHealingPolicyStatus {
     #[serde(default)]
     pub successful_healings: i64,
 
+    #[serde(default)]
+    pub success_ratio: Option<f64>,
+
     #[serde(default)]
     pub conditions: Vec<PolicyCondition>,
 }
@@ -325,3 +871,85 @@ pub struct PolicyCondition {
     #[serde(default)]
     pub message: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_batch_query() {
+        let pods = vec!["web-1".to_string(), "web-2".to_string()];
+
+        assert_eq!(
+            render_batch_query(
+                r#"sum(rate(errors{namespace="{namespace}", pod="{pod}"}[5m])) / sum(rate(requests{namespace="{namespace}", pod="{pod}"}[5m]))"#,
+                "default",
+                &pods
+            )
+            .as_deref(),
+            Some(
+                r#"sum by (pod) (rate(errors{namespace="default", pod=~"web-1|web-2"}[5m])) / sum by (pod) (rate(requests{namespace="default", pod=~"web-1|web-2"}[5m]))"#
+            )
+        );
+        assert_eq!(
+            render_batch_query(
+                r#"max(queue_ready{pod="{pod}"}) > max_over_time(queue_ready{pod="{pod}"}[1h])"#,
+                "default",
+                &pods
+            )
+            .as_deref(),
+            Some(
+                r#"max by (pod) (queue_ready{pod=~"web-1|web-2"}) > max_over_time(queue_ready{pod=~"web-1|web-2"}[1h])"#
+            )
+        );
+
+        assert!(render_batch_query(
+            r#"histogram_quantile(0.99, sum(rate(latency_bucket{pod="{pod}"}[5m])) by (le))"#,
+            "default",
+            &pods
+        )
+        .is_none());
+        assert!(
+            render_batch_query(r#"sum(up{namespace="{namespace}"})"#, "default", &pods).is_none()
+        );
+        assert!(render_batch_query(
+            r#"sum(errors{pod="{pod}"}) / sum(requests{namespace="{namespace}"})"#,
+            "default",
+            &pods
+        )
+        .is_none());
+        assert!(render_batch_query(
+            r#"avg(latency{namespace="{namespace}", pod!="{pod}"})"#,
+            "default",
+            &pods
+        )
+        .is_none());
+        assert!(render_batch_query(
+            r#"label_replace(up{pod="{pod}"}, "target", "{pod}", "", "")"#,
+            "default",
+            &pods
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_target_selector() {
+        let spec: SelfHealingPolicySpec = serde_json::from_value(serde_json::json!({
+            "targetLabels": { "app": "web", "tier": "frontend" },
+            "targetLabelExpressions": [
+                { "key": "env", "operator": "In", "values": ["prod", "staging"] },
+                { "key": "track", "operator": "NotIn", "values": ["canary"] },
+                { "key": "team", "operator": "Exists" },
+                { "key": "legacy", "operator": "DoesNotExist" }
+            ],
+            "thresholds": {},
+            "llmConfig": { "provider": "ollama", "model": "llama3", "apiKeySecret": "llm" }
+        }))
+        .unwrap();
+
+        assert_eq!(
+            spec.target_selector(),
+            "app=web,tier=frontend,env in (prod,staging),track notin (canary),team,!legacy"
+        );
+    }
+}
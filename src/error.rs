@@ -35,9 +35,15 @@ pub enum RecistError {
     #[error("Diagnosis failed: {0}")]
     DiagnosisError(String),
 
+    #[error("Containment failed: {0}")]
+    ContainmentError(String),
+
     #[error("Healing action failed: {0}")]
     HealingError(String),
 
+    #[error("Action refused to preserve availability: {0}")]
+    ActionRefused(String),
+
     #[error("Timeout: {0}")]
     Timeout(String),
 
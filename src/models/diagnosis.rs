@@ -204,3 +204,66 @@ pub struct LlmDiagnosisResponse {
     pub explanation: String,
     pub suggested_actions: Vec<String>,
 }
+
+impl From<&DiagnosisHypothesis> for crate::crd::DiagnosisResult {
+    fn from(h: &DiagnosisHypothesis) -> Self {
+        Self {
+            hypothesis: h.hypothesis.clone(),
+            confidence: h.confidence,
+            root_cause: h.root_cause.clone(),
+            evidence: h
+                .evidence
+                .iter()
+                .filter(|e| e.source != EvidenceSource::Log)
+                .map(|e| e.content.clone())
+                .collect(),
+            related_logs: h
+                .evidence
+                .iter()
+                .filter(|e| e.source == EvidenceSource::Log)
+                .map(|e| e.content.clone())
+                .collect(),
+        }
+    }
+}
+
+impl From<&CausalTree> for crate::crd::CausalGraph {
+    fn from(tree: &CausalTree) -> Self {
+        let mut nodes: Vec<crate::crd::CausalNode> = tree
+            .nodes
+            .values()
+            .map(|node| crate::crd::CausalNode {
+                id: node.id.clone(),
+                node_type: match node.node_type {
+                    CausalNodeType::Error => crate::crd::CausalNodeType::Error,
+                    CausalNodeType::Warning => crate::crd::CausalNodeType::Warning,
+                    CausalNodeType::Info => crate::crd::CausalNodeType::Event,
+                    CausalNodeType::Symptom => crate::crd::CausalNodeType::Symptom,
+                    CausalNodeType::RootCause => crate::crd::CausalNodeType::RootCause,
+                    CausalNodeType::Metric => crate::crd::CausalNodeType::Metric,
+                    CausalNodeType::Event => crate::crd::CausalNodeType::Event,
+                },
+                description: node.description.clone(),
+                timestamp: node.timestamp.to_rfc3339(),
+                severity: node.severity.clone(),
+                source: Some(node.source.clone()),
+            })
+            .collect();
+        nodes.sort_by(|a, b| a.id.cmp(&b.id));
+
+        Self {
+            nodes,
+            edges: tree
+                .edges
+                .iter()
+                .map(|edge| crate::crd::CausalEdge {
+                    from_node: edge.from_node_id.clone(),
+                    to_node: edge.to_node_id.clone(),
+                    relation_type: format!("{:?}", edge.relation),
+                    confidence: edge.confidence,
+                })
+                .collect(),
+            root_cause_node_id: tree.root_node_id.clone(),
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use super::{DiagnosisHypothesis, FaultCluster, KnowledgeEntry, SolutionStrategy};
+use super::{
+    ActionResult, DiagnosisHypothesis, FaultCluster, FaultContext, KnowledgeEntry,
+    SolutionStrategy, TrendAnalysis,
+};
 use crate::crd::{TriggerMetrics, TriggerReason};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -45,6 +48,24 @@ impl AgentEvent {
         pod_name: String,
         namespace: String,
         isolated: bool,
+    ) -> Self {
+        Self::containment_complete_with_method(
+            correlation_id,
+            pod_name,
+            namespace,
+            isolated.then(|| "NetworkPolicy".to_string()),
+            None,
+            None,
+        )
+    }
+
+    pub fn containment_complete_with_method(
+        correlation_id: Uuid,
+        pod_name: String,
+        namespace: String,
+        isolation_method: Option<String>,
+        refusal_reason: Option<String>,
+        downgrade_reason: Option<String>,
     ) -> Self {
         Self::new(
             AgentEventType::ContainmentComplete,
@@ -53,40 +74,45 @@ impl AgentEvent {
             EventPayload::ContainmentComplete(ContainmentCompletePayload {
                 pod_name,
                 namespace,
-                isolated,
-                isolation_method: if isolated {
-                    Some("NetworkPolicy".to_string())
-                } else {
-                    None
-                },
+                isolated: isolation_method.is_some(),
+                isolation_method,
+                refusal_reason,
+                downgrade_reason,
             }),
         )
     }
 
-    pub fn diagnosis_complete(correlation_id: Uuid, hypothesis: DiagnosisHypothesis) -> Self {
+    pub fn diagnosis_complete(
+        correlation_id: Uuid,
+        context: FaultContext,
+        hypothesis: DiagnosisHypothesis,
+    ) -> Self {
         Self::new(
             AgentEventType::DiagnosisComplete,
             AgentType::Diagnosis,
             correlation_id,
-            EventPayload::DiagnosisComplete(DiagnosisCompletePayload { hypothesis }),
+            EventPayload::DiagnosisComplete(DiagnosisCompletePayload {
+                context,
+                hypothesis,
+            }),
         )
     }
 
-    pub fn healing_complete(
-        correlation_id: Uuid,
-        strategy: SolutionStrategy,
-        success: bool,
-        message: String,
-    ) -> Self {
+    pub fn healing_complete(correlation_id: Uuid, healing: HealingCompletePayload) -> Self {
         Self::new(
             AgentEventType::HealingComplete,
             AgentType::MetaCognitive,
             correlation_id,
-            EventPayload::HealingComplete(HealingCompletePayload {
-                strategy,
-                success,
-                message,
-            }),
+            EventPayload::HealingComplete(healing),
+        )
+    }
+
+    pub fn proactive_warning(correlation_id: Uuid, warning: ProactiveWarningPayload) -> Self {
+        Self::new(
+            AgentEventType::ProactiveWarning,
+            AgentType::Containment,
+            correlation_id,
+            EventPayload::ProactiveWarning(warning),
         )
     }
 
@@ -152,18 +178,31 @@ pub struct ContainmentCompletePayload {
     pub namespace: String,
     pub isolated: bool,
     pub isolation_method: Option<String>,
+    #[serde(default)]
+    pub refusal_reason: Option<String>,
+    #[serde(default)]
+    pub downgrade_reason: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DiagnosisCompletePayload {
+    pub context: FaultContext,
     pub hypothesis: DiagnosisHypothesis,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct HealingCompletePayload {
+    pub context: FaultContext,
+    pub hypothesis: DiagnosisHypothesis,
     pub strategy: SolutionStrategy,
     pub success: bool,
     pub message: String,
+    #[serde(default)]
+    pub refusal_reason: Option<String>,
+    #[serde(default)]
+    pub action_results: Vec<ActionResult>,
+    #[serde(default)]
+    pub verification_started_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -179,6 +218,10 @@ pub struct ProactiveWarningPayload {
     pub message: String,
     pub suggested_action: Option<String>,
     pub confidence: f64,
+    #[serde(default)]
+    pub trend: Option<TrendAnalysis>,
+    #[serde(default)]
+    pub reason: Option<TriggerReason>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -1,13 +1,17 @@
-use crate::crd::{TriggerMetrics, TriggerReason};
-use chrono::{DateTime, Utc};
+use crate::crd::{ComparisonOperator, RuleSeverity, TriggerMetrics, TriggerReason};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::OwnerReference;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FaultCluster {
     pub faults: Vec<Fault>,
     pub detected_at: DateTime<Utc>,
     pub namespace: String,
+    #[serde(default)]
+    pub policy_ref: String,
 }
 
 impl FaultCluster {
@@ -16,9 +20,14 @@ impl FaultCluster {
             faults: Vec::new(),
             detected_at: Utc::now(),
             namespace,
+            policy_ref: String::new(),
         }
     }
 
+    pub fn set_policy_ref(&mut self, policy_ref: String) {
+        self.policy_ref = policy_ref;
+    }
+
     pub fn add_fault(&mut self, fault: Fault) {
         self.faults.push(fault);
     }
@@ -32,7 +41,59 @@ impl FaultCluster {
     }
 
     pub fn primary_fault(&self) -> Option<&Fault> {
-        self.faults.first()
+        self.faults.iter().min_by(|a, b| {
+            b.severity
+                .cmp(&a.severity)
+                .then(a.detected_at.cmp(&b.detected_at))
+        })
+    }
+
+    pub fn correlates_with(&self, fault: &Fault, window: ChronoDuration) -> bool {
+        self.faults.iter().any(|f| f.correlates_with(fault, window))
+    }
+
+    pub fn into_incidents(self, window: ChronoDuration) -> Vec<FaultCluster> {
+        let mut parents: Vec<usize> = (0..self.faults.len()).collect();
+
+        fn root(parents: &mut [usize], mut i: usize) -> usize {
+            while parents[i] != i {
+                parents[i] = parents[parents[i]];
+                i = parents[i];
+            }
+            i
+        }
+
+        for i in 0..self.faults.len() {
+            for j in (i + 1)..self.faults.len() {
+                if self.faults[i].correlates_with(&self.faults[j], window) {
+                    let (a, b) = (root(&mut parents, i), root(&mut parents, j));
+                    parents[b] = a;
+                }
+            }
+        }
+
+        let mut incidents: Vec<(usize, FaultCluster)> = Vec::new();
+        for (i, fault) in self.faults.into_iter().enumerate() {
+            let group = root(&mut parents, i);
+            match incidents.iter_mut().find(|(g, _)| *g == group) {
+                Some((_, incident)) => incident.add_fault(fault),
+                None => {
+                    let mut incident = FaultCluster {
+                        faults: Vec::new(),
+                        detected_at: self.detected_at,
+                        namespace: self.namespace.clone(),
+                        policy_ref: self.policy_ref.clone(),
+                    };
+                    incident.add_fault(fault);
+                    incidents.push((group, incident));
+                }
+            }
+        }
+
+        incidents
+            .into_iter()
+            .map(|(_, incident)| incident)
+            .collect()
     }
 }
 
@@ -45,6 +106,14 @@ pub struct Fault {
     pub detected_at: DateTime<Utc>,
     pub severity: FaultSeverity,
     pub labels: HashMap<String, String>,
+    #[serde(default)]
+    pub workload: Option<WorkloadRef>,
+    #[serde(default)]
+    pub signals: Vec<SignalReading>,
+    #[serde(default)]
+    pub node_name: Option<String>,
+    #[serde(default)]
+    pub services: Vec<String>,
 }
 
 impl Fault {
@@ -63,6 +132,49 @@ impl Fault {
             detected_at: Utc::now(),
             severity,
             labels: HashMap::new(),
+            workload: None,
+            signals: Vec::new(),
+            node_name: None,
+            services: Vec::new(),
+        }
+    }
+
+    pub fn set_placement(&mut self, node_name: Option<String>, services: Vec<String>) {
+        self.node_name = node_name;
+        self.services = services;
+    }
+
+    pub fn correlates_with(&self, other: &Fault, window: ChronoDuration) -> bool {
+        if self.namespace != other.namespace
+            || (self.detected_at - other.detected_at).abs() > window
+        {
+            return false;
+        }
+
+        let same_workload = self.workload.is_some() && self.workload == other.workload;
+        // Pods sharing a node only fail together when the node itself is at fault.
+        let same_node = self.node_name.is_some()
+            && self.node_name == other.node_name
+            && self
+                .reasons
+                .iter()
+                .any(|r| is_node_level(r) && other.reasons.contains(r));
+        let shared_service = self.services.iter().any(|s| other.services.contains(s));
+
+        same_workload || same_node || shared_service
+    }
+
+    pub fn set_workload(&mut self, workload: WorkloadRef) {
+        self.workload = Some(workload);
+    }
+
+    pub fn set_signals(&mut self, signals: Vec<SignalReading>) {
+        self.signals = signals;
+    }
+
+    pub fn escalate(&mut self, severity: FaultSeverity) {
+        if severity > self.severity {
+            self.severity = severity;
         }
     }
 
@@ -75,6 +187,16 @@ impl Fault {
             return FaultSeverity::Critical;
         }
 
+        let has_high = reasons.iter().any(|r| {
+            matches!(
+                r,
+                TriggerReason::ImagePullBackOff | TriggerReason::ProbeFailure
+            )
+        });
+        if has_high {
+            return FaultSeverity::High;
+        }
+
         let error_rate = metrics.error_rate.unwrap_or(0.0);
         if error_rate > 0.5 {
             return FaultSeverity::Critical;
@@ -97,6 +219,78 @@ impl Fault {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkloadRef {
+    #[serde(default)]
+    pub api_version: String,
+    pub kind: String,
+    pub name: String,
+}
+
+impl From<OwnerReference> for WorkloadRef {
+    fn from(owner: OwnerReference) -> Self {
+        Self {
+            api_version: owner.api_version,
+            kind: owner.kind,
+            name: owner.name,
+        }
+    }
+}
+
+impl std::fmt::Display for WorkloadRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.kind, self.name)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignalReading {
+    pub name: String,
+    pub value: f64,
+    pub operator: ComparisonOperator,
+    pub threshold: f64,
+    pub breached: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FaultContext {
+    pub policy_ref: String,
+    pub namespace: String,
+    pub pod_name: String,
+    pub workload: Option<WorkloadRef>,
+    pub reasons: Vec<TriggerReason>,
+    pub metrics: TriggerMetrics,
+    pub severity: FaultSeverity,
+    pub affected_pods: Vec<String>,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl FaultContext {
+    pub fn from_cluster(cluster: &FaultCluster) -> Option<Self> {
+        let primary = cluster.primary_fault()?;
+
+        Some(Self {
+            policy_ref: cluster.policy_ref.clone(),
+            namespace: primary.namespace.clone(),
+            pod_name: primary.pod_name.clone(),
+            workload: primary.workload.clone(),
+            reasons: primary.reasons.clone(),
+            metrics: primary.metrics.clone(),
+            severity: primary.severity.clone(),
+            affected_pods: cluster.pod_names(),
+            detected_at: cluster.detected_at,
+        })
+    }
+
+    pub fn primary_reason(&self) -> &TriggerReason {
+        self.reasons.first().unwrap_or(&TriggerReason::Unknown)
+    }
+
+    pub fn elapsed_ms(&self) -> i64 {
+        (Utc::now() - self.detected_at).num_milliseconds()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum FaultSeverity {
     Low,
@@ -116,13 +310,32 @@ impl std::fmt::Display for FaultSeverity {
     }
 }
 
+impl From<RuleSeverity> for FaultSeverity {
+    fn from(severity: RuleSeverity) -> Self {
+        match severity {
+            RuleSeverity::Low => FaultSeverity::Low,
+            RuleSeverity::Medium => FaultSeverity::Medium,
+            RuleSeverity::High => FaultSeverity::High,
+            RuleSeverity::Critical => FaultSeverity::Critical,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct IsolationRule {
     pub pod_name: String,
     pub namespace: String,
     pub network_policy_name: String,
+    #[serde(default)]
+    pub isolation_id: String,
+    #[serde(default)]
+    pub correlation_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub rule_type: IsolationRuleType,
+    #[serde(default)]
+    pub redirects: Vec<TrafficRedirect>,
+    #[serde(default)]
+    pub downgrade_reason: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -130,6 +343,16 @@ pub enum IsolationRuleType {
     DenyAll,
     DenyIngress,
     DenyEgress,
+    ServiceQuarantine,
+}
+
+impl IsolationRuleType {
+    pub fn method(&self) -> &'static str {
+        match self {
+            IsolationRuleType::ServiceQuarantine => "ServiceQuarantine",
+            _ => "NetworkPolicy",
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -137,6 +360,10 @@ pub struct TrafficRedirect {
     pub from_pod: String,
     pub to_pods: Vec<String>,
     pub namespace: String,
+    #[serde(default)]
+    pub service: String,
+    #[serde(default)]
+    pub weights: BTreeMap<String, u32>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -159,3 +386,78 @@ pub struct RejectedNeighbor {
     pub pod_name: String,
     pub reason: String,
 }
+
+fn is_node_level(reason: &TriggerReason) -> bool {
+    matches!(
+        reason,
+        TriggerReason::NetworkError | TriggerReason::ImagePullBackOff
+    )
+}
+
+#[cfg(test)]
+pub mod test_support {
+    use super::*;
+
+    pub fn fault(pod_name: &str, workload: &str, node_name: &str, reason: TriggerReason) -> Fault {
+        let mut fault = Fault::new(
+            pod_name.to_string(),
+            "default".to_string(),
+            vec![reason],
+            TriggerMetrics::default(),
+        );
+        fault.set_workload(WorkloadRef {
+            api_version: "apps/v1".to_string(),
+            kind: "Deployment".to_string(),
+            name: workload.to_string(),
+        });
+        fault.set_placement(Some(node_name.to_string()), Vec::new());
+        fault
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::fault;
+    use super::*;
+
+    #[test]
+    fn test_incidents_group_correlated_faults() {
+        let mut cluster = FaultCluster::new("default".to_string());
+        cluster.add_fault(fault("web-1", "web", "node-a", TriggerReason::HighCpu));
+        cluster.add_fault(fault("api-1", "api", "node-b", TriggerReason::HighLatency));
+        cluster.add_fault(fault("web-2", "web", "node-c", TriggerReason::OomKilled));
+        cluster.add_fault(fault(
+            "cache-1",
+            "cache",
+            "node-c",
+            TriggerReason::HighMemory,
+        ));
+        cluster.add_fault(fault(
+            "queue-1",
+            "queue",
+            "node-e",
+            TriggerReason::NetworkError,
+        ));
+        cluster.add_fault(fault("db-1", "db", "node-e", TriggerReason::NetworkError));
+
+        let incidents = cluster.into_incidents(ChronoDuration::seconds(120));
+        assert_eq!(incidents.len(), 4);
+
+        let web = incidents
+            .iter()
+            .find(|incident| incident.pod_names().contains(&"web-1".to_string()))
+            .unwrap();
+        assert_eq!(web.faults.len(), 2);
+        assert_eq!(web.primary_fault().unwrap().pod_name, "web-2");
+
+        let mut late = fault("web-3", "web", "node-d", TriggerReason::HighCpu);
+        late.detected_at = Utc::now() + ChronoDuration::minutes(10);
+        assert!(!web.correlates_with(&late, ChronoDuration::seconds(120)));
+
+        let node = incidents
+            .iter()
+            .find(|incident| incident.pod_names().contains(&"db-1".to_string()))
+            .unwrap();
+        assert_eq!(node.faults.len(), 2);
+    }
+}
@@ -1,7 +1,8 @@
+use super::WorkloadRef;
 use crate::crd::ActionType;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SolutionStrategy {
@@ -9,6 +10,8 @@ pub struct SolutionStrategy {
     pub actions: Vec<PlannedAction>,
     pub confidence: f64,
     pub risk_level: RiskLevel,
+    #[serde(default)]
+    pub risk_score: f64,
     pub estimated_duration_seconds: u64,
     pub rollback_plan: Option<RollbackPlan>,
     pub selected_at: DateTime<Utc>,
@@ -23,6 +26,7 @@ impl SolutionStrategy {
             strategy_type,
             actions: Vec::new(),
             confidence,
+            risk_score: risk_level.score(),
             risk_level,
             estimated_duration_seconds: estimated_duration,
             rollback_plan: None,
@@ -30,6 +34,18 @@ impl SolutionStrategy {
         }
     }
 
+    pub fn composite(steps: &[StrategyType], confidence: f64) -> Self {
+        let mut strategy = Self::new(StrategyType::Composite, confidence);
+        strategy.risk_score =
+            RiskLevel::combined_score(steps.iter().map(|step| step.default_risk_level()));
+        strategy.risk_level = RiskLevel::from_score(strategy.risk_score);
+        strategy.estimated_duration_seconds = steps
+            .iter()
+            .map(|step| step.estimated_duration_seconds())
+            .sum();
+        strategy
+    }
+
     pub fn add_action(&mut self, action: PlannedAction) {
         self.actions.push(action);
     }
@@ -37,6 +53,23 @@ impl SolutionStrategy {
     pub fn set_rollback_plan(&mut self, plan: RollbackPlan) {
         self.rollback_plan = Some(plan);
     }
+
+    pub fn capture_rollback_state(&mut self, results: &[ActionResult]) {
+        let plan = match self.rollback_plan.as_mut() {
+            Some(plan) => plan,
+            None => return,
+        };
+
+        for action in &mut plan.actions {
+            let captured = results
+                .iter()
+                .find(|result| result.order == action.order)
+                .and_then(|result| result.rollback_data.as_ref());
+            if let Some(original_state) = captured {
+                action.original_state = original_state.clone();
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -51,6 +84,13 @@ pub enum StrategyType {
 }
 
 impl StrategyType {
+    pub fn targets_workload(&self) -> bool {
+        matches!(
+            self,
+            StrategyType::HorizontalScale | StrategyType::VerticalScale
+        )
+    }
+
     pub fn default_risk_level(&self) -> RiskLevel {
         match self {
             StrategyType::PodRestart => RiskLevel::Low,
@@ -110,6 +150,36 @@ pub enum RiskLevel {
     Critical,
 }
 
+impl RiskLevel {
+    pub fn score(&self) -> f64 {
+        match self {
+            RiskLevel::Low => 0.05,
+            RiskLevel::Medium => 0.15,
+            RiskLevel::High => 0.35,
+            RiskLevel::Critical => 0.6,
+        }
+    }
+
+    pub fn from_score(score: f64) -> Self {
+        if score < 0.1 {
+            RiskLevel::Low
+        } else if score < 0.3 {
+            RiskLevel::Medium
+        } else if score < 0.55 {
+            RiskLevel::High
+        } else {
+            RiskLevel::Critical
+        }
+    }
+
+    pub fn combined_score(levels: impl IntoIterator<Item = RiskLevel>) -> f64 {
+        1.0 - levels
+            .into_iter()
+            .map(|level| 1.0 - level.score())
+            .product::<f64>()
+    }
+}
+
 impl std::fmt::Display for RiskLevel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -137,18 +207,66 @@ pub struct ActionTarget {
     pub namespace: String,
 }
 
+impl ActionTarget {
+    pub fn workload(&self) -> Option<WorkloadRef> {
+        let (api_version, kind) = match self.resource_type {
+            ResourceType::Deployment => ("apps/v1", "Deployment"),
+            ResourceType::StatefulSet => ("apps/v1", "StatefulSet"),
+            ResourceType::DaemonSet => ("apps/v1", "DaemonSet"),
+            ResourceType::ReplicaSet => ("apps/v1", "ReplicaSet"),
+            ResourceType::Job => ("batch/v1", "Job"),
+            ResourceType::Rollout => ("argoproj.io/v1alpha1", "Rollout"),
+            _ => return None,
+        };
+
+        Some(WorkloadRef {
+            api_version: api_version.to_string(),
+            kind: kind.to_string(),
+            name: self.name.clone(),
+        })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ResourceType {
     Pod,
     Deployment,
     StatefulSet,
     DaemonSet,
+    ReplicaSet,
+    Job,
+    Rollout,
     ConfigMap,
     Secret,
     Service,
     NetworkPolicy,
 }
 
+impl ResourceType {
+    pub fn from_kind(kind: &str) -> Option<Self> {
+        match kind {
+            "Pod" => Some(ResourceType::Pod),
+            "Deployment" => Some(ResourceType::Deployment),
+            "StatefulSet" => Some(ResourceType::StatefulSet),
+            "DaemonSet" => Some(ResourceType::DaemonSet),
+            "ReplicaSet" => Some(ResourceType::ReplicaSet),
+            "Job" => Some(ResourceType::Job),
+            "Rollout" => Some(ResourceType::Rollout),
+            _ => None,
+        }
+    }
+
+    pub fn is_scalable(&self) -> bool {
+        matches!(
+            self,
+            ResourceType::Deployment
+                | ResourceType::StatefulSet
+                | ResourceType::ReplicaSet
+                | ResourceType::Rollout
+        )
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RollbackPlan {
     pub actions: Vec<RollbackAction>,
@@ -157,11 +275,37 @@ pub struct RollbackPlan {
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RollbackAction {
+    #[serde(default)]
+    pub order: u32,
     pub action_type: RollbackActionType,
     pub target: ActionTarget,
     pub original_state: String,
 }
 
+impl RollbackAction {
+    pub fn captured_state(&self) -> Option<OriginalState> {
+        serde_json::from_str(&self.original_state).ok()
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum OriginalState {
+    Replicas {
+        workload: WorkloadRef,
+        replicas: i32,
+    },
+    Resources {
+        workload: WorkloadRef,
+        container: String,
+        resources: serde_json::Value,
+    },
+    Config {
+        name: String,
+        data: BTreeMap<String, Option<String>>,
+    },
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub enum RollbackActionType {
     RestoreReplicas,
@@ -195,9 +339,15 @@ pub struct StrategyEvaluation {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ActionResult {
     pub action_type: ActionType,
+    #[serde(default)]
+    pub order: u32,
     pub success: bool,
     pub message: String,
     pub executed_at: DateTime<Utc>,
     pub duration_ms: i64,
     pub rollback_data: Option<String>,
+    #[serde(default)]
+    pub refusal_reason: Option<String>,
+    #[serde(default)]
+    pub rolled_back: bool,
 }
@@ -31,6 +31,15 @@ impl HealingState {
     }
 
     pub fn transition_to(&mut self, target: HealingPhase, reason: Option<String>) -> Result<()> {
+        self.transition_at(target, reason, Utc::now())
+    }
+
+    pub fn transition_at(
+        &mut self,
+        target: HealingPhase,
+        reason: Option<String>,
+        timestamp: DateTime<Utc>,
+    ) -> Result<()> {
         if !self.is_valid_transition(&target) {
             return Err(RecistError::InvalidStateTransition {
                 from: self.phase.to_string(),
@@ -38,15 +47,15 @@ impl HealingState {
             });
         }
 
-        let now = Utc::now();
+        let timestamp = timestamp.max(self.updated_at);
         self.transitions.push(StateTransition {
             from: Some(self.phase.clone()),
             to: target.clone(),
-            timestamp: now,
+            timestamp,
             reason,
         });
         self.phase = target;
-        self.updated_at = now;
+        self.updated_at = timestamp;
         Ok(())
     }
 
@@ -65,6 +74,33 @@ impl HealingState {
         }
     }
 
+    pub fn from_history(created_at: DateTime<Utc>, transitions: Vec<StateTransition>) -> Self {
+        let mut state = Self::new();
+        state.created_at = created_at;
+        state.updated_at = created_at;
+        state.transitions[0].timestamp = created_at;
+
+        if let Some(last) = transitions.last() {
+            state.phase = last.to.clone();
+            state.updated_at = last.timestamp;
+            state.transitions = transitions;
+        }
+
+        state
+    }
+
+    pub fn phase_durations(&self) -> Vec<(HealingPhase, i64)> {
+        self.transitions
+            .windows(2)
+            .map(|pair| {
+                (
+                    pair[0].to.clone(),
+                    (pair[1].timestamp - pair[0].timestamp).num_milliseconds(),
+                )
+            })
+            .collect()
+    }
+
     pub fn is_terminal(&self) -> bool {
         matches!(self.phase, HealingPhase::Completed | HealingPhase::Failed)
     }
@@ -93,6 +129,30 @@ pub struct StateTransition {
     pub reason: Option<String>,
 }
 
+impl From<&StateTransition> for crate::crd::PhaseTransition {
+    fn from(t: &StateTransition) -> Self {
+        Self {
+            from: t.from.clone(),
+            to: t.to.clone(),
+            timestamp: t.timestamp.to_rfc3339(),
+            reason: t.reason.clone(),
+        }
+    }
+}
+
+impl From<&crate::crd::PhaseTransition> for StateTransition {
+    fn from(t: &crate::crd::PhaseTransition) -> Self {
+        Self {
+            from: t.from.clone(),
+            to: t.to.clone(),
+            timestamp: DateTime::parse_from_rfc3339(&t.timestamp)
+                .map(|ts| ts.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+            reason: t.reason.clone(),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct HealingContext {
     pub state: HealingState,
@@ -120,3 +180,41 @@ impl HealingContext {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_transition_history_and_phase_durations() {
+        let created_at = Utc::now() - Duration::seconds(30);
+        let mut state = HealingState::from_history(created_at, vec![]);
+
+        state
+            .transition_at(HealingPhase::Containing, None, created_at)
+            .unwrap();
+        state
+            .transition_at(
+                HealingPhase::Diagnosing,
+                None,
+                created_at + Duration::seconds(3),
+            )
+            .unwrap();
+
+        assert!(state.transition_to(HealingPhase::Completed, None).is_err());
+        assert_eq!(state.phase, HealingPhase::Diagnosing);
+
+        let restored = HealingState::from_history(created_at, state.transitions.clone());
+        assert_eq!(restored.phase, HealingPhase::Diagnosing);
+
+        let durations = restored.phase_durations();
+        assert_eq!(
+            durations,
+            vec![
+                (HealingPhase::Pending, 0),
+                (HealingPhase::Containing, 3_000),
+            ]
+        );
+    }
+}